@@ -0,0 +1,2 @@
+-- Tags are addressed by name in the API, so the name has to be unique
+ALTER TABLE tags ADD CONSTRAINT tags_tag_name_key UNIQUE (tag_name);
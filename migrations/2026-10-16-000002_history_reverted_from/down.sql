@@ -0,0 +1 @@
+ALTER TABLE glossary_history DROP COLUMN reverted_from;
@@ -0,0 +1 @@
+ALTER TABLE glossary_history ADD COLUMN reverted_from INTEGER;
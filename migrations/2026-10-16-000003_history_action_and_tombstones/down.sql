@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS glossary_tombstones;
+
+ALTER TABLE glossary_history DROP CONSTRAINT IF EXISTS glossary_history_action_check;
+ALTER TABLE glossary_history DROP COLUMN action;
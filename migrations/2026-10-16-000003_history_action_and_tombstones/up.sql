@@ -0,0 +1,23 @@
+-- What an history revision did to the glossary
+ALTER TABLE glossary_history ADD COLUMN action VARCHAR(16) NOT NULL DEFAULT 'update';
+
+UPDATE glossary_history SET action = 'create' WHERE revision = 0;
+UPDATE glossary_history SET action = 'revert' WHERE reverted_from IS NOT NULL;
+
+ALTER TABLE glossary_history ADD CONSTRAINT glossary_history_action_check
+    CHECK (action IN ('create', 'update', 'delete', 'revert', 'restore'));
+
+-- Deleted glossaries. No foreign key to glossary on purpose:
+-- the tombstone must outlive the glossary row.
+CREATE TABLE IF NOT EXISTS glossary_tombstones
+(
+   id          UUID             PRIMARY KEY,
+   glossary_id UUID             NOT NULL,
+   term        VARCHAR(255)     NOT NULL,
+   definition  TEXT             NOT NULL,
+   revision    INTEGER          NOT NULL,
+   who         VARCHAR(255),
+   deleted_at  TIMESTAMP        NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_glossary_tombstones_deleted_at ON glossary_tombstones(deleted_at DESC);
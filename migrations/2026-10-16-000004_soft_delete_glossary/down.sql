@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS idx_glossary_deleted_at;
+DROP INDEX IF EXISTS idx_glossary_term_active;
+
+DELETE FROM glossary WHERE deleted_at IS NOT NULL;
+ALTER TABLE glossary ADD CONSTRAINT glossary_term_key UNIQUE (term);
+
+ALTER TABLE glossary DROP COLUMN deleted_at;
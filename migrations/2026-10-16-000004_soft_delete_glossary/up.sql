@@ -0,0 +1,9 @@
+-- Deleted glossaries go to the trash first, and are purged later
+ALTER TABLE glossary ADD COLUMN deleted_at TIMESTAMP;
+
+-- A glossary in the trash should not block creating the same term again
+ALTER TABLE glossary DROP CONSTRAINT IF EXISTS glossary_term_key;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_glossary_term_active ON glossary(term) WHERE deleted_at IS NULL;
+
+-- Index for listing and purging the trash
+CREATE INDEX IF NOT EXISTS idx_glossary_deleted_at ON glossary(deleted_at) WHERE deleted_at IS NOT NULL;
@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS idx_glossary_search_vector;
+DROP TRIGGER IF EXISTS glossary_search_vector_update ON glossary;
+DROP FUNCTION IF EXISTS glossary_search_vector_update();
+DROP FUNCTION IF EXISTS glossary_search_vector(TEXT, TEXT);
+ALTER TABLE glossary DROP COLUMN search_vector;
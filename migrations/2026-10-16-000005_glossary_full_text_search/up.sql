@@ -0,0 +1,28 @@
+-- Full-text search on term and definition, accent-insensitive
+CREATE EXTENSION IF NOT EXISTS unaccent;
+
+ALTER TABLE glossary ADD COLUMN search_vector tsvector;
+
+-- Term matches weight A, definition matches weight B, so `ts_rank`
+-- ranks a term match above a definition match
+CREATE OR REPLACE FUNCTION glossary_search_vector(term TEXT, definition TEXT) RETURNS tsvector AS $$
+BEGIN
+    RETURN setweight(to_tsvector('english', unaccent(coalesce(term, ''))), 'A') ||
+           setweight(to_tsvector('english', unaccent(coalesce(definition, ''))), 'B');
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION glossary_search_vector_update() RETURNS trigger AS $$
+BEGIN
+    NEW.search_vector := glossary_search_vector(NEW.term, NEW.definition);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER glossary_search_vector_update
+    BEFORE INSERT OR UPDATE OF term, definition ON glossary
+    FOR EACH ROW EXECUTE PROCEDURE glossary_search_vector_update();
+
+UPDATE glossary SET search_vector = glossary_search_vector(term, definition);
+
+CREATE INDEX IF NOT EXISTS idx_glossary_search_vector ON glossary USING GIN(search_vector);
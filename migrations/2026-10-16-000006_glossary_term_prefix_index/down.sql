@@ -0,0 +1 @@
+DROP INDEX IF EXISTS idx_glossary_term_lower_prefix;
@@ -0,0 +1,4 @@
+-- Case-insensitive prefix lookup for the suggest API: lower(term) LIKE 'ku%'
+CREATE INDEX IF NOT EXISTS idx_glossary_term_lower_prefix
+    ON glossary (lower(term) text_pattern_ops)
+    WHERE deleted_at IS NULL;
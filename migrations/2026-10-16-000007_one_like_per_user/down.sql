@@ -0,0 +1 @@
+ALTER TABLE likes DROP CONSTRAINT IF EXISTS likes_glossary_id_who_key;
@@ -0,0 +1,16 @@
+-- Keep only the first like of each user on a glossary
+DELETE FROM likes
+WHERE id IN (
+    SELECT id FROM (
+        SELECT id, row_number() OVER (
+            PARTITION BY glossary_id, who ORDER BY created_at ASC, id ASC
+        ) AS n
+        FROM likes
+        WHERE who IS NOT NULL
+    ) AS duplicates
+    WHERE n > 1
+);
+
+-- One like per user on a glossary. Anonymous likes are not accepted anymore,
+-- the ones recorded before (who IS NULL) are kept as they are.
+ALTER TABLE likes ADD CONSTRAINT likes_glossary_id_who_key UNIQUE (glossary_id, who);
@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS idx_likes_glossary_id_kind;
+DELETE FROM likes WHERE kind <> 'helpful';
+ALTER TABLE likes DROP CONSTRAINT IF EXISTS likes_glossary_id_who_kind_key;
+ALTER TABLE likes ADD CONSTRAINT likes_glossary_id_who_key UNIQUE (glossary_id, who);
+ALTER TABLE likes DROP COLUMN kind;
@@ -0,0 +1,11 @@
+-- Likes become reactions of a kind, the existing likes are "helpful" reactions
+ALTER TABLE likes
+ADD COLUMN kind VARCHAR(16) NOT NULL DEFAULT 'helpful'
+    CHECK (kind IN ('helpful', 'unclear', 'outdated'));
+
+-- One reaction of each kind per user on a glossary
+ALTER TABLE likes DROP CONSTRAINT IF EXISTS likes_glossary_id_who_key;
+ALTER TABLE likes ADD CONSTRAINT likes_glossary_id_who_kind_key UNIQUE (glossary_id, who, kind);
+
+-- Count reactions of each kind per glossary
+CREATE INDEX IF NOT EXISTS idx_likes_glossary_id_kind ON likes(glossary_id, kind);
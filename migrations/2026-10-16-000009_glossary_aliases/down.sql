@@ -0,0 +1,18 @@
+DROP TRIGGER IF EXISTS glossary_aliases_search_vector_update ON glossary_aliases;
+DROP FUNCTION IF EXISTS glossary_aliases_search_vector_update();
+
+CREATE OR REPLACE FUNCTION glossary_search_vector_update() RETURNS trigger AS $$
+BEGIN
+    NEW.search_vector := glossary_search_vector(NEW.term, NEW.definition);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+DROP FUNCTION IF EXISTS glossary_search_terms(UUID, TEXT);
+
+DROP TRIGGER IF EXISTS glossary_term_check_alias ON glossary;
+DROP FUNCTION IF EXISTS glossary_term_check_alias();
+
+DROP TABLE IF EXISTS glossary_aliases;
+DROP FUNCTION IF EXISTS glossary_alias_check_term();
+
+UPDATE glossary SET search_vector = glossary_search_vector(term, definition);
@@ -0,0 +1,78 @@
+-- Other names a glossary is known by: "k8s" and "kube" for "Kubernetes"
+CREATE TABLE IF NOT EXISTS glossary_aliases
+(
+    id          UUID PRIMARY KEY,
+    glossary_id UUID         NOT NULL REFERENCES glossary (id) ON DELETE CASCADE,
+    alias       VARCHAR(255) NOT NULL,
+    created_at  TIMESTAMP    NOT NULL DEFAULT NOW()
+);
+
+-- An alias names one glossary only, whatever its case
+CREATE UNIQUE INDEX IF NOT EXISTS idx_glossary_aliases_alias ON glossary_aliases (lower(alias));
+CREATE INDEX IF NOT EXISTS idx_glossary_aliases_alias_prefix ON glossary_aliases (lower(alias) text_pattern_ops);
+CREATE INDEX IF NOT EXISTS idx_glossary_aliases_glossary_id ON glossary_aliases (glossary_id);
+
+-- An alias can not be the term of a glossary...
+CREATE OR REPLACE FUNCTION glossary_alias_check_term() RETURNS trigger AS $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM glossary WHERE deleted_at IS NULL AND lower(term) = lower(NEW.alias)
+    ) THEN
+        RAISE EXCEPTION 'Alias "%" is already a glossary term', NEW.alias
+            USING ERRCODE = 'unique_violation';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER glossary_alias_check_term
+    BEFORE INSERT OR UPDATE OF alias ON glossary_aliases
+    FOR EACH ROW EXECUTE PROCEDURE glossary_alias_check_term();
+
+-- ...and a term can not be the alias of a glossary
+CREATE OR REPLACE FUNCTION glossary_term_check_alias() RETURNS trigger AS $$
+BEGIN
+    IF NEW.deleted_at IS NULL AND EXISTS (
+        SELECT 1 FROM glossary_aliases
+        INNER JOIN glossary ON glossary.id = glossary_aliases.glossary_id
+        WHERE glossary.deleted_at IS NULL AND lower(glossary_aliases.alias) = lower(NEW.term)
+    ) THEN
+        RAISE EXCEPTION 'Term "%" is already a glossary alias', NEW.term
+            USING ERRCODE = 'unique_violation';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER glossary_term_check_alias
+    BEFORE INSERT OR UPDATE OF term, deleted_at ON glossary
+    FOR EACH ROW EXECUTE PROCEDURE glossary_term_check_alias();
+
+-- Aliases are searched like the term, with weight A
+CREATE OR REPLACE FUNCTION glossary_search_terms(glossary_id UUID, term TEXT) RETURNS TEXT AS $$
+    SELECT concat_ws(' ', term, string_agg(alias, ' '))
+    FROM glossary_aliases
+    WHERE glossary_aliases.glossary_id = glossary_search_terms.glossary_id;
+$$ LANGUAGE sql STABLE;
+
+CREATE OR REPLACE FUNCTION glossary_search_vector_update() RETURNS trigger AS $$
+BEGIN
+    NEW.search_vector := glossary_search_vector(glossary_search_terms(NEW.id, NEW.term), NEW.definition);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE FUNCTION glossary_aliases_search_vector_update() RETURNS trigger AS $$
+DECLARE
+    changed_id UUID := CASE WHEN TG_OP = 'DELETE' THEN OLD.glossary_id ELSE NEW.glossary_id END;
+BEGIN
+    UPDATE glossary
+    SET search_vector = glossary_search_vector(glossary_search_terms(id, term), definition)
+    WHERE id = changed_id;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER glossary_aliases_search_vector_update
+    AFTER INSERT OR UPDATE OR DELETE ON glossary_aliases
+    FOR EACH ROW EXECUTE PROCEDURE glossary_aliases_search_vector_update();
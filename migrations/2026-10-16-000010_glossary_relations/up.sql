@@ -0,0 +1,14 @@
+-- Typed links between glossaries: "see also", broader/narrower, contrast, replacement.
+-- Every link but replaced-by is stored in both directions, broader with narrower.
+CREATE TABLE IF NOT EXISTS glossary_relations
+(
+    source_id  UUID        NOT NULL REFERENCES glossary (id) ON DELETE CASCADE,
+    target_id  UUID        NOT NULL REFERENCES glossary (id) ON DELETE CASCADE,
+    relation   VARCHAR(16) NOT NULL
+        CHECK (relation IN ('related', 'broader', 'narrower', 'antonym', 'replaced-by')),
+    created_at TIMESTAMP   NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (source_id, target_id, relation),
+    CHECK (source_id <> target_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_glossary_relations_target_id ON glossary_relations (target_id);
@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS idx_glossary_term_domain_active;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_glossary_term_active ON glossary (term) WHERE deleted_at IS NULL;
+
+ALTER TABLE glossary_history DROP COLUMN domain;
+ALTER TABLE glossary DROP COLUMN domain;
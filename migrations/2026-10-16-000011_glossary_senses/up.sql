@@ -0,0 +1,9 @@
+-- A term can have several senses, one per domain: "pipeline" for the data team
+-- and for the CI team. Each sense is a glossary with its own definition and history.
+-- The empty domain is the general sense.
+ALTER TABLE glossary ADD COLUMN domain VARCHAR(100) NOT NULL DEFAULT '';
+ALTER TABLE glossary_history ADD COLUMN domain VARCHAR(100) NOT NULL DEFAULT '';
+
+DROP INDEX IF EXISTS idx_glossary_term_active;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_glossary_term_domain_active
+    ON glossary (term, domain) WHERE deleted_at IS NULL;
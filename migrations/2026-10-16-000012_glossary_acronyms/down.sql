@@ -0,0 +1,33 @@
+DROP FUNCTION IF EXISTS glossary_search_query(TEXT);
+
+CREATE OR REPLACE FUNCTION glossary_aliases_search_vector_update() RETURNS trigger AS $$
+DECLARE
+    changed_id UUID := CASE WHEN TG_OP = 'DELETE' THEN OLD.glossary_id ELSE NEW.glossary_id END;
+BEGIN
+    UPDATE glossary
+    SET search_vector = glossary_search_vector(glossary_search_terms(id, term), definition)
+    WHERE id = changed_id;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS glossary_search_vector_update ON glossary;
+CREATE TRIGGER glossary_search_vector_update
+    BEFORE INSERT OR UPDATE OF term, definition ON glossary
+    FOR EACH ROW EXECUTE PROCEDURE glossary_search_vector_update();
+
+CREATE OR REPLACE FUNCTION glossary_search_vector_update() RETURNS trigger AS $$
+BEGIN
+    NEW.search_vector := glossary_search_vector(glossary_search_terms(NEW.id, NEW.term), NEW.definition);
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP INDEX IF EXISTS idx_glossary_abbreviation;
+
+ALTER TABLE glossary_history DROP COLUMN expansion;
+ALTER TABLE glossary_history DROP COLUMN abbreviation;
+ALTER TABLE glossary DROP COLUMN expansion;
+ALTER TABLE glossary DROP COLUMN abbreviation;
+
+UPDATE glossary SET search_vector = glossary_search_vector(glossary_search_terms(id, term), definition);
@@ -0,0 +1,58 @@
+-- An acronym and what it stands for: "API" and "Application Programming Interface".
+-- The term is one side of the pair, usually the abbreviation.
+ALTER TABLE glossary ADD COLUMN abbreviation VARCHAR(50);
+ALTER TABLE glossary ADD COLUMN expansion VARCHAR(255);
+ALTER TABLE glossary_history ADD COLUMN abbreviation VARCHAR(50);
+ALTER TABLE glossary_history ADD COLUMN expansion VARCHAR(255);
+
+CREATE INDEX IF NOT EXISTS idx_glossary_abbreviation ON glossary (lower(abbreviation))
+    WHERE abbreviation IS NOT NULL;
+
+-- The abbreviation and the expansion are searched like the term, with weight A
+CREATE OR REPLACE FUNCTION glossary_search_vector_update() RETURNS trigger AS $$
+BEGIN
+    NEW.search_vector := glossary_search_vector(
+        concat_ws(' ', glossary_search_terms(NEW.id, NEW.term), NEW.abbreviation, NEW.expansion),
+        NEW.definition
+    );
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS glossary_search_vector_update ON glossary;
+CREATE TRIGGER glossary_search_vector_update
+    BEFORE INSERT OR UPDATE OF term, definition, abbreviation, expansion ON glossary
+    FOR EACH ROW EXECUTE PROCEDURE glossary_search_vector_update();
+
+CREATE OR REPLACE FUNCTION glossary_aliases_search_vector_update() RETURNS trigger AS $$
+DECLARE
+    changed_id UUID := CASE WHEN TG_OP = 'DELETE' THEN OLD.glossary_id ELSE NEW.glossary_id END;
+BEGIN
+    UPDATE glossary
+    SET search_vector = glossary_search_vector(
+        concat_ws(' ', glossary_search_terms(id, term), abbreviation, expansion),
+        definition
+    )
+    WHERE id = changed_id;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Searching an acronym also finds what mentions its expansion, and the reverse
+CREATE OR REPLACE FUNCTION glossary_search_query(q TEXT) RETURNS tsquery AS $$
+DECLARE
+    result tsquery := websearch_to_tsquery('english', unaccent(q));
+    counterpart TEXT;
+BEGIN
+    FOR counterpart IN
+        SELECT CASE WHEN lower(abbreviation) = lower(trim(q)) THEN expansion ELSE abbreviation END
+        FROM glossary
+        WHERE deleted_at IS NULL
+          AND abbreviation IS NOT NULL AND expansion IS NOT NULL
+          AND (lower(abbreviation) = lower(trim(q)) OR lower(expansion) = lower(trim(q)))
+    LOOP
+        result := result || phraseto_tsquery('english', unaccent(counterpart));
+    END LOOP;
+    RETURN result;
+END;
+$$ LANGUAGE plpgsql STABLE;
@@ -0,0 +1,15 @@
+-- Translations of the term and definition of a glossary, one per BCP-47 locale
+-- ("vi", "ja-JP"). A translation is outdated once the source definition changes,
+-- until someone updates it.
+CREATE TABLE IF NOT EXISTS glossary_translations
+(
+    glossary_id     UUID         NOT NULL REFERENCES glossary (id) ON DELETE CASCADE,
+    locale          VARCHAR(35)  NOT NULL,
+    term            VARCHAR(255) NOT NULL,
+    definition      TEXT         NOT NULL,
+    source_revision INT          NOT NULL,
+    outdated        BOOLEAN      NOT NULL DEFAULT FALSE,
+    who             VARCHAR(255),
+    updated_at      TIMESTAMP    NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (glossary_id, locale)
+);
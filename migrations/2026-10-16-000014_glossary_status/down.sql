@@ -0,0 +1,13 @@
+DROP FUNCTION IF EXISTS glossary_status_weight(TEXT);
+
+ALTER TABLE glossary_history
+    DROP COLUMN replaced_by,
+    DROP COLUMN status_reason,
+    DROP COLUMN status;
+
+ALTER TABLE glossary
+    DROP CONSTRAINT glossary_replaced_by_other,
+    DROP CONSTRAINT glossary_status_reason,
+    DROP COLUMN replaced_by,
+    DROP COLUMN status_reason,
+    DROP COLUMN status;
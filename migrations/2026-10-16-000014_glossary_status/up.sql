@@ -0,0 +1,19 @@
+-- Steer readers off legacy terms without deleting them: a deprecated or banned
+-- glossary says why, and may point to the glossary to use instead.
+ALTER TABLE glossary
+    ADD COLUMN status VARCHAR(16) NOT NULL DEFAULT 'active'
+        CHECK (status IN ('active', 'deprecated', 'banned')),
+    ADD COLUMN status_reason TEXT,
+    ADD COLUMN replaced_by UUID REFERENCES glossary (id) ON DELETE SET NULL,
+    ADD CONSTRAINT glossary_status_reason CHECK (status = 'active' OR status_reason IS NOT NULL),
+    ADD CONSTRAINT glossary_replaced_by_other CHECK (replaced_by <> id);
+
+ALTER TABLE glossary_history
+    ADD COLUMN status VARCHAR(16) NOT NULL DEFAULT 'active',
+    ADD COLUMN status_reason TEXT,
+    ADD COLUMN replaced_by UUID;
+
+-- Deprecated and banned glossaries are ranked lower in search
+CREATE OR REPLACE FUNCTION glossary_status_weight(status TEXT) RETURNS REAL AS $$
+    SELECT CASE status WHEN 'active' THEN 1.0 WHEN 'deprecated' THEN 0.1 ELSE 0.05 END::REAL;
+$$ LANGUAGE sql IMMUTABLE;
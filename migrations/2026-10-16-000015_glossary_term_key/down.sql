@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_glossary_term_key_active;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_glossary_term_domain_active
+    ON glossary (term, domain) WHERE deleted_at IS NULL;
@@ -0,0 +1,32 @@
+-- Terms are stored NFC-normalized with single spaces, and are unique whatever
+-- their case: "API", "api" and "Api " are the same term.
+DROP INDEX IF EXISTS idx_glossary_term_domain_active;
+
+UPDATE glossary
+SET term = btrim(regexp_replace(normalize(term, NFC), '\s+', ' ', 'g'))
+WHERE term <> btrim(regexp_replace(normalize(term, NFC), '\s+', ' ', 'g'));
+
+-- Glossaries sharing a term whatever its case are not picked from automatically,
+-- they have to be renamed, merged or deleted before migrating
+DO $$
+DECLARE
+    conflicts TEXT;
+BEGIN
+    SELECT string_agg(ids, '; ') INTO conflicts
+    FROM (
+        SELECT string_agg(id::text, ', ' ORDER BY created_at, id) AS ids
+        FROM glossary
+        WHERE deleted_at IS NULL
+        GROUP BY lower(term), lower(domain)
+        HAVING count(*) > 1
+    ) AS duplicates;
+
+    IF conflicts IS NOT NULL THEN
+        RAISE EXCEPTION 'Glossaries share the same term, rename or delete them first: %',
+            conflicts;
+    END IF;
+END;
+$$;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_glossary_term_key_active
+    ON glossary (lower(term), lower(domain)) WHERE deleted_at IS NULL;
@@ -0,0 +1,9 @@
+DROP TRIGGER IF EXISTS glossary_slug_update ON glossary;
+DROP FUNCTION IF EXISTS glossary_slug_update();
+
+DROP TABLE IF EXISTS glossary_slug_redirects;
+
+DROP INDEX IF EXISTS idx_glossary_slug;
+ALTER TABLE glossary DROP COLUMN slug;
+
+DROP FUNCTION IF EXISTS glossary_slugify(TEXT, TEXT);
@@ -0,0 +1,85 @@
+-- A readable, unique identifier for links: "application-programming-interface".
+-- The sense of a term other than the general one gets its domain appended.
+CREATE OR REPLACE FUNCTION glossary_slugify(term TEXT, domain TEXT) RETURNS TEXT AS $$
+    SELECT coalesce(
+        nullif(btrim(regexp_replace(
+            lower(unaccent(concat_ws(' ', term, nullif(domain, '')))),
+            '[^a-z0-9]+', '-', 'g'
+        ), '-'), ''),
+        'term'
+    );
+$$ LANGUAGE sql STABLE;
+
+ALTER TABLE glossary ADD COLUMN slug VARCHAR(300);
+
+-- Glossaries sharing a slug are told apart by a suffix, the oldest keeps it bare
+UPDATE glossary
+SET slug = slugs.slug
+FROM (
+    SELECT id, CASE WHEN n = 1 THEN base ELSE base || '-' || n END AS slug
+    FROM (
+        SELECT id, glossary_slugify(term, domain) AS base, row_number() OVER (
+            PARTITION BY glossary_slugify(term, domain) ORDER BY created_at ASC, id ASC
+        ) AS n
+        FROM glossary
+    ) AS bases
+) AS slugs
+WHERE glossary.id = slugs.id;
+
+ALTER TABLE glossary ALTER COLUMN slug SET NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_glossary_slug ON glossary (slug);
+
+-- The slugs a glossary had before being renamed, they redirect to the current one
+CREATE TABLE IF NOT EXISTS glossary_slug_redirects
+(
+    slug        VARCHAR(300) PRIMARY KEY,
+    glossary_id UUID         NOT NULL REFERENCES glossary (id) ON DELETE CASCADE,
+    created_at  TIMESTAMP    NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_glossary_slug_redirects_glossary_id
+    ON glossary_slug_redirects (glossary_id);
+
+-- The slug follows the term and the domain. It is generated when left empty on
+-- insert, and a rename keeps the previous slug as a redirect.
+CREATE OR REPLACE FUNCTION glossary_slug_update() RETURNS trigger AS $$
+DECLARE
+    base      TEXT;
+    candidate TEXT;
+    n         INT := 1;
+BEGIN
+    IF TG_OP = 'INSERT' AND coalesce(NEW.slug, '') <> '' THEN
+        RETURN NEW;
+    END IF;
+    IF TG_OP = 'UPDATE' AND NEW.term = OLD.term AND NEW.domain = OLD.domain THEN
+        RETURN NEW;
+    END IF;
+
+    base := glossary_slugify(NEW.term, NEW.domain);
+    candidate := base;
+    WHILE EXISTS (SELECT 1 FROM glossary WHERE slug = candidate AND id <> NEW.id)
+       OR EXISTS (
+           SELECT 1 FROM glossary_slug_redirects
+           WHERE slug = candidate AND glossary_id <> NEW.id
+       )
+    LOOP
+        n := n + 1;
+        candidate := base || '-' || n;
+    END LOOP;
+
+    IF TG_OP = 'UPDATE' AND OLD.slug <> candidate THEN
+        INSERT INTO glossary_slug_redirects (slug, glossary_id)
+        VALUES (OLD.slug, NEW.id)
+        ON CONFLICT (slug) DO NOTHING;
+        DELETE FROM glossary_slug_redirects WHERE slug = candidate;
+    END IF;
+
+    NEW.slug := candidate;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS glossary_slug_update ON glossary;
+CREATE TRIGGER glossary_slug_update
+    BEFORE INSERT OR UPDATE OF term, domain ON glossary
+    FOR EACH ROW EXECUTE PROCEDURE glossary_slug_update();
@@ -0,0 +1,12 @@
+UPDATE glossary_history SET action = 'update' WHERE action = 'merge';
+
+ALTER TABLE glossary_history DROP CONSTRAINT IF EXISTS glossary_history_action_check;
+ALTER TABLE glossary_history ADD CONSTRAINT glossary_history_action_check
+    CHECK (action IN ('create', 'update', 'delete', 'revert', 'restore'));
+
+ALTER TABLE glossary_history DROP COLUMN merged_from;
+
+DROP TRIGGER IF EXISTS glossary_term_redirect_update ON glossary;
+DROP FUNCTION IF EXISTS glossary_term_redirect_update();
+
+DROP TABLE IF EXISTS glossary_term_redirects;
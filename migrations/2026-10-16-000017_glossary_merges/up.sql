@@ -0,0 +1,41 @@
+-- The terms a glossary had before being renamed or merged into another one.
+-- A lookup by term falls back to them, they never block a new term.
+CREATE TABLE IF NOT EXISTS glossary_term_redirects
+(
+    id          UUID PRIMARY KEY,
+    glossary_id UUID         NOT NULL REFERENCES glossary (id) ON DELETE CASCADE,
+    term        VARCHAR(255) NOT NULL,
+    created_at  TIMESTAMP    NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_glossary_term_redirects_term
+    ON glossary_term_redirects (lower(term));
+CREATE INDEX IF NOT EXISTS idx_glossary_term_redirects_glossary_id
+    ON glossary_term_redirects (glossary_id);
+
+-- A renamed glossary keeps its previous term as a redirect, a term in use is
+-- no longer one
+CREATE OR REPLACE FUNCTION glossary_term_redirect_update() RETURNS trigger AS $$
+BEGIN
+    DELETE FROM glossary_term_redirects WHERE lower(term) = lower(NEW.term);
+    IF TG_OP = 'UPDATE' AND lower(NEW.term) <> lower(OLD.term) THEN
+        INSERT INTO glossary_term_redirects (id, glossary_id, term)
+        VALUES (gen_random_uuid(), NEW.id, OLD.term)
+        ON CONFLICT (lower(term)) DO UPDATE SET glossary_id = EXCLUDED.glossary_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS glossary_term_redirect_update ON glossary;
+CREATE TRIGGER glossary_term_redirect_update
+    AFTER INSERT OR UPDATE OF term ON glossary
+    FOR EACH ROW EXECUTE PROCEDURE glossary_term_redirect_update();
+
+-- The revisions moved over from a glossary merged into this one, and the
+-- merge revision itself, point to the merged glossary
+ALTER TABLE glossary_history ADD COLUMN merged_from UUID;
+
+ALTER TABLE glossary_history DROP CONSTRAINT IF EXISTS glossary_history_action_check;
+ALTER TABLE glossary_history ADD CONSTRAINT glossary_history_action_check
+    CHECK (action IN ('create', 'update', 'delete', 'revert', 'restore', 'merge'));
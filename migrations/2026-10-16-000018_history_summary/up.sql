@@ -0,0 +1,2 @@
+-- Why an edit was made, like a commit message or a wiki edit summary
+ALTER TABLE glossary_history ADD COLUMN summary VARCHAR(500);
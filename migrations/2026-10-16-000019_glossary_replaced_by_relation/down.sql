@@ -0,0 +1,3 @@
+-- The replaced-by relations written by the trigger are kept
+DROP TRIGGER IF EXISTS glossary_replaced_by_update ON glossary;
+DROP FUNCTION IF EXISTS glossary_replaced_by_update();
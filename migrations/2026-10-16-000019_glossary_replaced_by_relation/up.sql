@@ -0,0 +1,33 @@
+-- The replacement of a deprecated or banned glossary is also its replaced-by
+-- relation. The relation follows glossary.replaced_by, whoever writes it.
+CREATE OR REPLACE FUNCTION glossary_replaced_by_update() RETURNS trigger AS $$
+BEGIN
+    DELETE FROM glossary_relations
+    WHERE source_id = NEW.id AND relation = 'replaced-by'
+      AND target_id IS DISTINCT FROM NEW.replaced_by;
+    IF NEW.replaced_by IS NOT NULL THEN
+        INSERT INTO glossary_relations (source_id, target_id, relation)
+        VALUES (NEW.id, NEW.replaced_by, 'replaced-by')
+        ON CONFLICT DO NOTHING;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS glossary_replaced_by_update ON glossary;
+CREATE TRIGGER glossary_replaced_by_update
+    AFTER INSERT OR UPDATE OF replaced_by ON glossary
+    FOR EACH ROW EXECUTE PROCEDURE glossary_replaced_by_update();
+
+-- Bring the existing replaced-by relations in line with the glossaries
+DELETE FROM glossary_relations
+USING glossary
+WHERE glossary_relations.source_id = glossary.id
+  AND glossary_relations.relation = 'replaced-by'
+  AND glossary_relations.target_id IS DISTINCT FROM glossary.replaced_by;
+
+INSERT INTO glossary_relations (source_id, target_id, relation)
+SELECT id, replaced_by, 'replaced-by'
+FROM glossary
+WHERE replaced_by IS NOT NULL
+ON CONFLICT DO NOTHING;
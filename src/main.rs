@@ -62,6 +62,12 @@ async fn main() -> std::io::Result<()> {
     let conn = &mut pool.get().expect("could not get db connection from pool");
     conn.run_pending_migrations(MIGRATIONS).expect("failed to migration");
 
+    // Purge the trash on start, it can also be purged with `DELETE /api/v1/trash`
+    let retention_days = v1::trash::trash_retention_days();
+    if let Err(err) = v1::trash::purge_trash(conn, retention_days) {
+        log::error!("Failed to purge the trash: {}", err);
+    }
+
     let server = HttpServer::new(move || {
         let cors = Cors::default()
             .allow_any_header()
@@ -98,13 +104,47 @@ async fn main() -> std::io::Result<()> {
                     .service(v1::glossary::list)
                     .service(v1::glossary::list_popular)
                     .service(v1::glossary::search)
+                    .service(v1::glossary::suggest)
+                    .service(v1::glossary::get_by_slug)
+                    .service(v1::glossary::get_by_term)
                     .service(v1::glossary::get)
                     .service(v1::glossary::update)
+                    .service(v1::glossary::patch)
                     .service(v1::glossary::delete)
                     .service(v1::glossary::create)
+                    .service(v1::glossary::revert)
+                    .service(v1::glossary::merge_into)
+                    .service(v1::acronym::list)
+                    .service(v1::alias::list)
+                    .service(v1::alias::add)
+                    .service(v1::alias::remove)
+                    .service(v1::translation::list)
+                    .service(v1::translation::save)
+                    .service(v1::translation::remove)
+                    .service(v1::relation::list)
+                    .service(v1::relation::add)
+                    .service(v1::relation::remove)
+                    .service(v1::glossary_history::list)
+                    .service(v1::glossary_history::get)
+                    .service(v1::glossary_history::diff)
+                    .service(v1::trash::list)
+                    .service(v1::trash::restore)
+                    .service(v1::trash::purge)
+                    .service(v1::tombstone::list)
                     .service(v1::like::list)
                     .service(v1::like::plus_one)
-                    .service(v1::like::minus_one),
+                    .service(v1::like::minus_one)
+                    .service(v1::reaction::react)
+                    .service(v1::reaction::unreact)
+                    .service(v1::reaction::report)
+                    .service(v1::tag::list)
+                    .service(v1::tag::create)
+                    .service(v1::tag::list_glossary)
+                    .service(v1::tag::get)
+                    .service(v1::tag::update)
+                    .service(v1::tag::delete)
+                    .service(v1::tag::attach)
+                    .service(v1::tag::detach),
             )
     })
     .bind(listen.to_string())?
@@ -10,7 +10,17 @@ use thiserror::Error;
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ListResp<T> {
     pub results: Vec<T>,
+    /// Number of results in this page
     pub count: i32,
+    /// Number of results across all pages, only on lists which count them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total: Option<i64>,
+    /// Pass as `cursor` to get the next page, none on the last page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// Link to the next page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<String>,
 }
 
 impl<T> Default for ListResp<T>
@@ -30,6 +40,9 @@ where
         Self {
             results: vec![],
             count: 0,
+            total: None,
+            next_cursor: None,
+            next: None,
         }
     }
 
@@ -37,8 +50,47 @@ where
         Self {
             results: results.to_vec(),
             count: results.len() as i32,
+            total: None,
+            next_cursor: None,
+            next: None,
         }
     }
+
+    pub fn with_total(self, total: i64) -> Self {
+        Self {
+            total: Some(total),
+            ..self
+        }
+    }
+
+    pub fn with_next(self, next_cursor: String, next: String) -> Self {
+        Self {
+            next_cursor: Some(next_cursor),
+            next: Some(next),
+            ..self
+        }
+    }
+}
+
+/// Encode the sort key of the last result of a page as an opaque cursor
+pub fn encode_cursor(key: &str) -> String {
+    key.bytes().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn decode_cursor(cursor: &str) -> Result<String, ApiError> {
+    let invalid = || ApiError::invalid_input("Invalid cursor");
+
+    let bytes = (0..cursor.len())
+        .step_by(2)
+        .map(|i| {
+            cursor
+                .get(i..i + 2)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        })
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(invalid)?;
+
+    String::from_utf8(bytes).map_err(|_| invalid())
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -63,12 +115,19 @@ pub enum ApiError {
     #[error("Invalid input: {0}")]
     InvalidInput(String),
 
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    /// The optional value is returned as `details` so clients can resolve the conflict
     #[error("Conflict: {0}")]
-    Conflict(String),
+    Conflict(String, Option<serde_json::Value>),
 
     #[error("Unprocessable entity: {0}")]
     UnprocessableEntity(String),
 
+    #[error("Unsupported media type: {0}")]
+    UnsupportedMediaType(String),
+
     #[error("Internal server error: {0}")]
     InternalError(String),
 
@@ -85,8 +144,20 @@ impl ApiError {
         ApiError::InvalidInput(msg.to_string())
     }
 
+    pub fn unauthorized(msg: &str) -> Self {
+        ApiError::Unauthorized(msg.to_string())
+    }
+
     pub fn conflict(msg: &str) -> Self {
-        ApiError::Conflict(msg.to_string())
+        ApiError::Conflict(msg.to_string(), None)
+    }
+
+    pub fn conflict_with<T: Serialize>(msg: &str, details: &T) -> Self {
+        ApiError::Conflict(msg.to_string(), serde_json::to_value(details).ok())
+    }
+
+    pub fn unsupported_media_type(msg: &str) -> Self {
+        ApiError::UnsupportedMediaType(msg.to_string())
     }
 
     pub fn internal(msg: &str) -> Self {
@@ -94,8 +165,14 @@ impl ApiError {
     }
 
     fn to_error_resp(&self) -> ErrorResp {
+        let details = match self {
+            ApiError::Conflict(_, details) => details.clone(),
+            _ => None,
+        };
+
         ErrorResp {
             error: self.to_string(),
+            details,
         }
     }
 }
@@ -106,8 +183,10 @@ impl ResponseError for ApiError {
         match self {
             ApiError::NotFound(_) => StatusCode::NOT_FOUND,
             ApiError::InvalidInput(_) => StatusCode::BAD_REQUEST,
-            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::Conflict(..) => StatusCode::CONFLICT,
             ApiError::UnprocessableEntity(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             ApiError::InternalError(_) | ApiError::DatabaseError(_) => {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
@@ -126,11 +205,9 @@ impl From<DieselError> for ApiError {
         match error {
             DieselError::NotFound => ApiError::NotFound("Resource not found".to_string()),
             DieselError::DatabaseError(kind, info) => match kind {
-                DatabaseErrorKind::UniqueViolation => {
-                    ApiError::Conflict("Resource already exists".to_string())
-                }
+                DatabaseErrorKind::UniqueViolation => ApiError::conflict("Resource already exists"),
                 DatabaseErrorKind::ForeignKeyViolation => {
-                    ApiError::Conflict("Foreign key constraint violation".to_string())
+                    ApiError::conflict("Foreign key constraint violation")
                 }
                 _ => ApiError::DatabaseError(format!("Database error: {}", info.message())),
             },
@@ -149,18 +226,22 @@ impl From<BlockingError> for ApiError {
 #[derive(Debug, Error, Deserialize, Serialize)]
 pub struct ErrorResp {
     pub error: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
 }
 
 impl ErrorResp {
     pub fn new(error: &str) -> Self {
         Self {
             error: error.to_string(),
+            details: None,
         }
     }
 
     pub fn from(error: diesel::result::Error) -> Self {
         Self {
             error: error.to_string(),
+            details: None,
         }
     }
 }
@@ -180,6 +261,7 @@ impl ResponseError for ErrorResp {
         let status_code = self.status_code();
         let json_response = ErrorResp {
             error: self.error.clone(),
+            details: self.details.clone(),
         };
 
         actix_web::HttpResponse::build(status_code).json(json_response)
@@ -190,6 +272,7 @@ impl std::convert::From<BlockingError> for ErrorResp {
     fn from(error: BlockingError) -> Self {
         Self {
             error: error.to_string(),
+            details: None,
         }
     }
 }
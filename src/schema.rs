@@ -6,6 +6,23 @@ table! {
         revision -> Int4,
         created_at -> Timestamp,
         updated_at -> Timestamp,
+        deleted_at -> Nullable<Timestamp>,
+        domain -> Varchar,
+        abbreviation -> Nullable<Varchar>,
+        expansion -> Nullable<Varchar>,
+        status -> Varchar,
+        status_reason -> Nullable<Text>,
+        replaced_by -> Nullable<Uuid>,
+        slug -> Varchar,
+    }
+}
+
+table! {
+    glossary_aliases (id) {
+        id -> Uuid,
+        glossary_id -> Uuid,
+        alias -> Varchar,
+        created_at -> Timestamp,
     }
 }
 
@@ -18,6 +35,37 @@ table! {
         who -> Nullable<Varchar>,
         created_at -> Timestamp,
         glossary_id -> Uuid,
+        reverted_from -> Nullable<Int4>,
+        action -> Varchar,
+        domain -> Varchar,
+        abbreviation -> Nullable<Varchar>,
+        expansion -> Nullable<Varchar>,
+        status -> Varchar,
+        status_reason -> Nullable<Text>,
+        replaced_by -> Nullable<Uuid>,
+        merged_from -> Nullable<Uuid>,
+        summary -> Nullable<Varchar>,
+    }
+}
+
+table! {
+    glossary_term_redirects (id) {
+        id -> Uuid,
+        glossary_id -> Uuid,
+        term -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    glossary_tombstones (id) {
+        id -> Uuid,
+        glossary_id -> Uuid,
+        term -> Varchar,
+        definition -> Text,
+        revision -> Int4,
+        who -> Nullable<Varchar>,
+        deleted_at -> Timestamp,
     }
 }
 
@@ -27,10 +75,73 @@ table! {
         created_at -> Timestamp,
         glossary_id -> Uuid,
         who -> Nullable<Varchar>,
+        kind -> Varchar,
+    }
+}
+
+table! {
+    glossary_relations (source_id, target_id, relation) {
+        source_id -> Uuid,
+        target_id -> Uuid,
+        relation -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    glossary_slug_redirects (slug) {
+        slug -> Varchar,
+        glossary_id -> Uuid,
+        created_at -> Timestamp,
+    }
+}
+
+table! {
+    glossary_tags (glossary_id, tag_id) {
+        glossary_id -> Uuid,
+        tag_id -> Uuid,
+    }
+}
+
+table! {
+    glossary_translations (glossary_id, locale) {
+        glossary_id -> Uuid,
+        locale -> Varchar,
+        term -> Varchar,
+        definition -> Text,
+        source_revision -> Int4,
+        outdated -> Bool,
+        who -> Nullable<Varchar>,
+        updated_at -> Timestamp,
+    }
+}
+
+table! {
+    tags (id) {
+        id -> Uuid,
+        tag_name -> Varchar,
     }
 }
 
+joinable!(glossary_aliases -> glossary (glossary_id));
 joinable!(glossary_history -> glossary (glossary_id));
+joinable!(glossary_slug_redirects -> glossary (glossary_id));
+joinable!(glossary_tags -> glossary (glossary_id));
+joinable!(glossary_tags -> tags (tag_id));
+joinable!(glossary_term_redirects -> glossary (glossary_id));
+joinable!(glossary_translations -> glossary (glossary_id));
 joinable!(likes -> glossary (glossary_id));
 
-allow_tables_to_appear_in_same_query!(glossary, glossary_history, likes,);
+allow_tables_to_appear_in_same_query!(
+    glossary,
+    glossary_aliases,
+    glossary_history,
+    glossary_relations,
+    glossary_slug_redirects,
+    glossary_tags,
+    glossary_term_redirects,
+    glossary_tombstones,
+    glossary_translations,
+    likes,
+    tags,
+);
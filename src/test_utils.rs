@@ -1,4 +1,5 @@
 use crate::diesel::{Connection, RunQueryDsl};
+use chrono::Utc;
 use diesel::{
     pg::PgConnection,
     r2d2::{ConnectionManager, Pool},
@@ -6,8 +7,28 @@ use diesel::{
 };
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
 use std::env;
+use uuid::Uuid;
 
-use crate::DBPool;
+use crate::{schema::glossary, v1::glossary::GlossaryDB, DBPool};
+
+/// Call a service and check it returns a JSON success, then give back the response
+#[cfg(test)]
+macro_rules! service_should_ok_and_return_json {
+    ($app:expr, $req:expr) => {{
+        let req = actix_web::test::TestRequest::from($req).to_request();
+        let resp = actix_web::test::call_service(&$app, req).await;
+
+        assert!(resp.status().is_success());
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "application/json"
+        );
+
+        resp
+    }};
+}
+#[cfg(test)]
+pub(crate) use service_should_ok_and_return_json;
 
 pub struct TestContext {
     conn: PgConnection,
@@ -85,3 +106,21 @@ impl Drop for TestContext {
             .unwrap_or_else(|_| panic!("Couldn't drop database {}", self.db_name));
     }
 }
+
+/// Insert a glossary straight into the database, without history
+pub fn insert_glossary(conn: &mut PgConnection, term: &str, definition: &str) -> Uuid {
+    let glossary_id = Uuid::new_v4();
+    diesel::insert_into(glossary::table)
+        .values(GlossaryDB {
+            id: glossary_id,
+            term: term.to_string(),
+            definition: definition.to_string(),
+            created_at: Utc::now().naive_utc(),
+            updated_at: Utc::now().naive_utc(),
+            ..Default::default()
+        })
+        .execute(conn)
+        .expect("could not insert glossary");
+
+    glossary_id
+}
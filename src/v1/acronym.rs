@@ -0,0 +1,145 @@
+use actix_web::{get, web, Responder};
+use diesel::{
+    pg::PgConnection, result::Error, ExpressionMethods, NullableExpressionMethods, QueryDsl,
+    Queryable, RunQueryDsl,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::glossary::lower;
+use crate::{
+    response::{ApiError, ListResp},
+    DBPool,
+};
+
+pub type Acronyms = ListResp<Acronym>;
+
+/// An abbreviation and what it stands for, with the glossary defining them
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq, Queryable)]
+pub struct Acronym {
+    pub id: Uuid,
+    pub term: String,
+    pub abbreviation: String,
+    pub expansion: String,
+}
+
+/// Every glossary with both an abbreviation and an expansion, by abbreviation
+pub fn list_acronyms(conn: &mut PgConnection) -> Result<Vec<Acronym>, Error> {
+    use crate::schema::glossary::dsl::*;
+
+    glossary
+        .filter(deleted_at.is_null())
+        .filter(abbreviation.is_not_null())
+        .filter(expansion.is_not_null())
+        .select((
+            id,
+            term,
+            abbreviation.assume_not_null(),
+            expansion.assume_not_null(),
+        ))
+        .order((
+            lower(abbreviation.assume_not_null()).asc(),
+            expansion.asc(),
+        ))
+        .load::<Acronym>(conn)
+}
+
+/// The acronyms index `/acronyms`
+#[get("/acronyms")]
+pub async fn list(pool: web::Data<DBPool>) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+
+    let acronyms = web::block(move || list_acronyms(&mut conn)).await??;
+    Ok(web::Json(Acronyms::from(&acronyms)))
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{service_should_ok_and_return_json, TestContext};
+    use crate::v1::glossary::{create, search, Glossaries, Glossary, GlossaryRequest};
+    use actix_web::{test, App};
+
+    // The other side of the pair defaults to the term. An acronym without an
+    // expansion is created with a warning in the body. The index lists complete pairs only.
+    #[actix_rt::test]
+    async fn create_and_list_acronyms() {
+        let ctx = TestContext::new("create_and_list_acronyms");
+        let pool = web::Data::new(ctx.get_pool());
+
+        let app = test::init_service(App::new().app_data(pool).service(create).service(list)).await;
+
+        let requests = [
+            ("API", None, Some("Application Programming Interface")),
+            ("Continuous Integration", Some("CI"), None),
+            ("SLA", None, None),
+        ];
+        for (term, abbreviation, expansion) in requests {
+            let req = test::TestRequest::post()
+                .uri("/glossary")
+                .set_json(&GlossaryRequest {
+                    term: Some(term.to_string()),
+                    definition: Some("test_definition".to_string()),
+                    abbreviation: abbreviation.map(str::to_string),
+                    expansion: expansion.map(str::to_string),
+                    ..Default::default()
+                });
+            let resp = service_should_ok_and_return_json!(app, req);
+            let created: Glossary = test::read_body_json(resp).await;
+
+            if term == "SLA" {
+                assert_eq!(created.warnings, vec!["SLA looks like an acronym, add its expansion"]);
+                assert_eq!(created.abbreviation, None);
+            } else {
+                assert!(created.warnings.is_empty());
+                assert!(created.abbreviation.is_some() && created.expansion.is_some());
+            }
+        }
+
+        let req = test::TestRequest::get().uri("/acronyms");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let acronyms: Acronyms = test::read_body_json(resp).await;
+        assert_eq!(acronyms.count, 2);
+        assert_eq!(acronyms.results[0].abbreviation, "API");
+        assert_eq!(acronyms.results[0].expansion, "Application Programming Interface");
+        assert_eq!(acronyms.results[1].abbreviation, "CI");
+        assert_eq!(acronyms.results[1].term, "Continuous Integration");
+    }
+
+    // Searching an acronym finds what mentions its expansion, and the reverse
+    #[actix_rt::test]
+    async fn acronyms_are_searchable() {
+        let ctx = TestContext::new("acronyms_are_searchable");
+        let pool = web::Data::new(ctx.get_pool());
+
+        let app =
+            test::init_service(App::new().app_data(pool).service(create).service(search)).await;
+
+        let requests = [
+            ("API", Some("Application Programming Interface"), "A contract between programs"),
+            ("REST", None, "An architectural style for an application programming interface"),
+            ("Webhook", None, "An HTTP callback, an API calling you"),
+        ];
+        for (term, expansion, definition) in requests {
+            let req = test::TestRequest::post()
+                .uri("/glossary")
+                .set_json(&GlossaryRequest {
+                    term: Some(term.to_string()),
+                    definition: Some(definition.to_string()),
+                    expansion: expansion.map(str::to_string),
+                    ..Default::default()
+                });
+            let _ = service_should_ok_and_return_json!(app, req);
+        }
+
+        for q in ["api", "application%20programming%20interface"] {
+            let req = test::TestRequest::get().uri(&format!("/glossary-search?q={}", q));
+            let resp = service_should_ok_and_return_json!(app, req);
+            let page: Glossaries = test::read_body_json(resp).await;
+            let mut terms: Vec<String> = page.results.into_iter().map(|g| g.term).collect();
+            terms.sort();
+            assert_eq!(terms, vec!["API", "REST", "Webhook"], "searching {}", q);
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use actix_web::{delete, get, post, web, Responder};
+use chrono::{NaiveDateTime, Utc};
+use diesel::{
+    pg::PgConnection,
+    result::{DatabaseErrorKind, Error},
+    ExpressionMethods, Insertable, QueryDsl, Queryable, RunQueryDsl,
+};
+use std::{collections::HashMap, str::FromStr};
+use uuid::Uuid;
+
+use super::glossary::{get_glossary, lower, normalize_term};
+use crate::{
+    response::{ApiError, Message},
+    schema::*,
+    DBPool,
+};
+
+#[derive(Queryable, Insertable)]
+#[diesel(table_name = glossary_aliases)]
+pub struct AliasDB {
+    pub id: Uuid,
+    pub glossary_id: Uuid,
+    pub alias: String,
+    pub created_at: NaiveDateTime,
+}
+
+/// Aliases are stored as typed, normalized like the terms.
+/// They are matched whatever their case.
+pub fn normalize_alias(alias: &str) -> String {
+    normalize_term(&ammonia::clean(alias.trim()))
+}
+
+/// Aliases of one glossary, sorted by alias
+pub fn list_aliases(conn: &mut PgConnection, _glossary_id: Uuid) -> Result<Vec<String>, Error> {
+    use crate::schema::glossary_aliases::dsl::*;
+
+    glossary_aliases
+        .filter(glossary_id.eq(_glossary_id))
+        .select(alias)
+        .order(alias.asc())
+        .load::<String>(conn)
+}
+
+/// Aliases of many glossaries at once, keyed by glossary id
+pub fn list_aliases_by_glossary(
+    conn: &mut PgConnection,
+    glossary_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<String>>, Error> {
+    use crate::schema::glossary_aliases::dsl::*;
+
+    let rows = glossary_aliases
+        .filter(glossary_id.eq_any(glossary_ids))
+        .select((glossary_id, alias))
+        .order(alias.asc())
+        .load::<(Uuid, String)>(conn)?;
+
+    let mut aliases_by_glossary: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for (_glossary_id, _alias) in rows {
+        aliases_by_glossary
+            .entry(_glossary_id)
+            .or_default()
+            .push(_alias);
+    }
+
+    Ok(aliases_by_glossary)
+}
+
+/// Add an alias to a glossary. The database rejects an alias which already is
+/// a term or an alias of any glossary, including this one.
+pub fn add_alias(
+    conn: &mut PgConnection,
+    _glossary_id: Uuid,
+    name: &str,
+) -> Result<Vec<String>, ApiError> {
+    use crate::schema::glossary_aliases::dsl::*;
+
+    // Glossaries in the trash can not get new aliases
+    get_glossary(conn, _glossary_id)?;
+
+    let name = normalize_alias(name);
+    let inserted = diesel::insert_into(glossary_aliases)
+        .values(AliasDB {
+            id: Uuid::new_v4(),
+            glossary_id: _glossary_id,
+            alias: name.clone(),
+            created_at: Utc::now().naive_utc(),
+        })
+        .execute(conn);
+
+    match inserted {
+        Ok(_) => Ok(list_aliases(conn, _glossary_id)?),
+        Err(Error::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => Err(
+            ApiError::conflict(&format!("\"{}\" is already a glossary term or alias", name)),
+        ),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn remove_alias(
+    conn: &mut PgConnection,
+    _glossary_id: Uuid,
+    name: &str,
+) -> Result<usize, Error> {
+    use crate::schema::glossary_aliases::dsl::*;
+
+    diesel::delete(
+        glossary_aliases
+            .filter(glossary_id.eq(_glossary_id))
+            .filter(lower(alias).eq(lower(normalize_alias(name)))),
+    )
+    .execute(conn)
+}
+
+/// List the aliases of a glossary `/glossary/{id}/aliases`
+#[get("/glossary/{glossary_id}/aliases")]
+pub async fn list(
+    id: web::Path<String>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+
+    let glossary_id =
+        Uuid::from_str(&id).map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+
+    let aliases = web::block(move || list_aliases(&mut conn, glossary_id)).await??;
+    Ok(web::Json(aliases))
+}
+
+/// Add an alias to a glossary `/glossary/{id}/aliases/{alias}`.
+/// Returns the aliases of the glossary.
+#[post("/glossary/{glossary_id}/aliases/{alias}")]
+pub async fn add(
+    path: web::Path<(String, String)>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let (id, name) = path.into_inner();
+
+    let glossary_id =
+        Uuid::from_str(&id).map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+    if normalize_alias(&name).is_empty() {
+        return Err(ApiError::invalid_input("Alias cannot be empty"));
+    }
+
+    let aliases = web::block(move || add_alias(&mut conn, glossary_id, &name)).await??;
+    Ok(web::Json(aliases))
+}
+
+/// Remove an alias from a glossary `/glossary/{id}/aliases/{alias}`
+#[delete("/glossary/{glossary_id}/aliases/{alias}")]
+pub async fn remove(
+    path: web::Path<(String, String)>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let (id, name) = path.into_inner();
+
+    let glossary_id =
+        Uuid::from_str(&id).map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+
+    web::block(move || remove_alias(&mut conn, glossary_id, &name)).await??;
+    Ok(web::Json(Message::new("ok")))
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{insert_glossary, service_should_ok_and_return_json, TestContext};
+    use crate::v1::glossary::{
+        get, get_glossary_by_term, search, suggest, Glossaries, Glossary, GlossaryDB,
+        Suggestion,
+    };
+    use actix_web::{http, test, App};
+
+    // Add aliases to a glossary, they are returned with the glossary.
+    // An alias which already is a term or an alias is a 409 CONFLICT.
+    #[actix_rt::test]
+    async fn add_and_remove_aliases() {
+        let ctx = TestContext::new("add_and_remove_aliases");
+        let pool = ctx.get_pool();
+        let conn = &mut pool.get().expect("could not get db connection from pool");
+
+        let kubernetes = insert_glossary(conn, "Kubernetes", "Container orchestration");
+        let docker = insert_glossary(conn, "Docker", "Container runtime");
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(list)
+                .service(add)
+                .service(remove)
+                .service(get),
+        )
+        .await;
+
+        for alias in ["k8s", "kube"] {
+            let req = test::TestRequest::post()
+                .uri(&format!("/glossary/{}/aliases/{}", kubernetes, alias));
+            let _ = service_should_ok_and_return_json!(app, req);
+        }
+
+        let req = test::TestRequest::get().uri(&format!("/glossary/{}", kubernetes));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let glossary: Glossary = test::read_body_json(resp).await;
+        assert_eq!(glossary.aliases, vec!["k8s", "kube"]);
+
+        // Already an alias (whatever the case), already a term, or the own term
+        for (glossary_id, alias) in [(docker, "K8S"), (docker, "kubernetes"), (docker, "docker")] {
+            let req = test::TestRequest::post()
+                .uri(&format!("/glossary/{}/aliases/{}", glossary_id, alias))
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+        }
+
+        // A term can not be an alias either
+        let created = diesel::insert_into(glossary::table)
+            .values(GlossaryDB {
+                id: Uuid::new_v4(),
+                term: "kube".to_string(),
+                definition: "test_definition".to_string(),
+                ..Default::default()
+            })
+            .execute(conn);
+        assert!(matches!(
+            created.map_err(ApiError::from),
+            Err(ApiError::Conflict(..))
+        ));
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/glossary/{}/aliases/KUBE", kubernetes));
+        let _ = service_should_ok_and_return_json!(app, req);
+
+        let req = test::TestRequest::get().uri(&format!("/glossary/{}/aliases", kubernetes));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let aliases: Vec<String> = test::read_body_json(resp).await;
+        assert_eq!(aliases, vec!["k8s"]);
+    }
+
+    // Search, suggest and lookup by term match the aliases
+    #[actix_rt::test]
+    async fn aliases_are_searchable() {
+        let ctx = TestContext::new("aliases_are_searchable");
+        let pool = ctx.get_pool();
+        let conn = &mut pool.get().expect("could not get db connection from pool");
+
+        let kubernetes = insert_glossary(conn, "Kubernetes", "Container orchestration");
+        insert_glossary(conn, "Docker", "Container runtime");
+        add_alias(conn, kubernetes, "k8s").unwrap();
+
+        let found = get_glossary_by_term(conn, "K8s").unwrap();
+        assert_eq!(found.id, kubernetes);
+        let found = get_glossary_by_term(conn, "kubernetes").unwrap();
+        assert_eq!(found.id, kubernetes);
+        assert!(get_glossary_by_term(conn, "k9s").is_err());
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(search)
+                .service(suggest),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/glossary-search?q=k8s");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let page: Glossaries = test::read_body_json(resp).await;
+        assert_eq!(page.total, Some(1));
+        assert_eq!(page.results[0].term, "Kubernetes");
+
+        let req = test::TestRequest::get().uri("/glossary-suggest?prefix=K8");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let suggestions: Vec<Suggestion> = test::read_body_json(resp).await;
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].id, kubernetes);
+    }
+}
@@ -1,39 +1,100 @@
-use actix_web::{delete, get, post, put, web, HttpRequest, Responder};
+use actix_web::{
+    delete, get,
+    http::header::{self, EntityTag, ETag},
+    patch, post, put, web, HttpMessage, HttpRequest, HttpResponse, Responder,
+};
 use actix_web_validator::Json;
 use ammonia::clean;
 use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
 use diesel::{
-    pg::PgConnection, result::Error, BoolExpressionMethods, ExpressionMethods, Insertable,
-    PgTextExpressionMethods, QueryDsl, Queryable, RunQueryDsl,
+    pg::PgConnection,
+    result::Error,
+    sql_query,
+    sql_types::{BigInt, Double, Float, Nullable, Text, Timestamp},
+    BoolExpressionMethods, Connection, ExpressionMethods, Insertable, OptionalExtension, QueryDsl,
+    Queryable, QueryableByName, RunQueryDsl,
 };
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{collections::HashMap, str::FromStr};
+use unicode_normalization::UnicodeNormalization;
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 use super::{
-    glossary_history::{create_glossary_history, list_glossary_history},
-    like::{list_likes, Like},
+    alias::list_aliases_by_glossary,
+    glossary_history::{
+        create_glossary_history, get_glossary_revision, list_last_editors,
+        merge_glossary_history, HistoryAction,
+    },
+    like::{list_liked_by, list_likes, Like},
+    reaction::{count_reactions_by_glossary, ReactionCounts, ReactionKind},
+    relation::{list_relations, move_relations, Relation},
+    tag::{list_glossary_tag_names, list_tag_names_by_glossary},
+    tombstone::create_tombstone,
+    translation::{
+        mark_translations_outdated, move_translations, preferred_locales, translate_glossaries,
+        LangQuery,
+    },
 };
 use crate::{
-    response::{ApiError, ListResp, Message},
+    response::{decode_cursor, encode_cursor, ApiError, ListResp, Message},
     schema::*,
     DBPool,
 };
 
+#[diesel::declare_sql_function]
+extern "SQL" {
+    /// SQL `lower()`, to compare terms whatever their case
+    fn lower(x: diesel::sql_types::Text) -> diesel::sql_types::Text;
+}
+
 pub type Glossaries = ListResp<Glossary>;
 
 #[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
 pub struct Glossary {
     pub id: String,
     pub term: String,
+    /// Readable and unique, generated from the term: `/glossary/by-slug/{slug}`
+    pub slug: String,
+    /// The domain of this sense of the term, `None` for the general sense
+    pub domain: Option<String>,
+    /// The acronym side of the term, "API" for "Application Programming Interface"
+    pub abbreviation: Option<String>,
+    /// What the abbreviation stands for
+    pub expansion: Option<String>,
     pub definition: String,
+    /// The locale the term and definition are translated to, `None` in the source language
+    pub locale: Option<String>,
+    /// Set when the translation was made for an earlier definition
+    pub translation_outdated: bool,
+    pub status: GlossaryStatus,
+    /// Why the glossary is deprecated or banned
+    pub status_reason: Option<String>,
+    /// The glossary to use instead of a deprecated or banned one
+    pub replaced_by: Option<String>,
     pub revision: i32,
+    /// Only loaded for a single glossary, lists only carry `likes_count`
     pub likes: Vec<Like>,
     pub likes_count: i32,
+    /// Number of reactions of each kind, `helpful` ones are the likes
+    pub reactions: ReactionCounts,
+    /// Whether the authenticated user likes this glossary
+    pub liked_by_me: bool,
     pub who: Option<String>,
+    pub tags: Vec<String>,
+    /// Other names the glossary is known by
+    pub aliases: Vec<String>,
+    /// Only loaded for a single glossary, with the related terms resolved
+    pub relations: Vec<Relation>,
+    /// Only loaded for a single glossary, every sense of its term including itself
+    pub senses: Vec<Sense>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set when the glossary is in the trash
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// Hints on a saved glossary, e.g. an acronym created without its expansion
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
 impl Glossary {
@@ -41,13 +102,30 @@ impl Glossary {
         Self {
             id: Uuid::new_v4().to_string(),
             term,
+            slug: String::new(),
+            domain: None,
+            abbreviation: None,
+            expansion: None,
             definition,
+            locale: None,
+            translation_outdated: false,
+            status: GlossaryStatus::Active,
+            status_reason: None,
+            replaced_by: None,
             revision: 0,
             likes: vec![],
             likes_count: 0,
+            reactions: ReactionCounts::default(),
+            liked_by_me: false,
             who: None,
+            tags: vec![],
+            aliases: vec![],
+            relations: vec![],
+            senses: vec![],
             created_at: Utc::now(),
             updated_at: Utc::now(),
+            deleted_at: None,
+            warnings: vec![],
         }
     }
 
@@ -59,6 +137,14 @@ impl Glossary {
             revision: self.revision,
             created_at: self.created_at.naive_utc(),
             updated_at: self.updated_at.naive_utc(),
+            deleted_at: None,
+            domain: self.domain.clone().unwrap_or_default(),
+            abbreviation: self.abbreviation.clone().filter(|a| !a.is_empty()),
+            expansion: self.expansion.clone().filter(|e| !e.is_empty()),
+            status: self.status.as_str().to_string(),
+            status_reason: self.status_reason.clone(),
+            replaced_by: self.replaced_by.as_deref().and_then(|r| Uuid::from_str(r).ok()),
+            slug: self.slug.clone(),
         }
     }
 
@@ -77,9 +163,16 @@ impl Glossary {
             ..self.clone()
         }
     }
+
+    pub fn add_tags(&self, tags: Vec<String>) -> Self {
+        Self {
+            tags,
+            ..self.clone()
+        }
+    }
 }
 
-#[derive(Queryable, Insertable)]
+#[derive(Queryable, QueryableByName, Insertable)]
 #[diesel(table_name = glossary)]
 pub struct GlossaryDB {
     pub id: Uuid,
@@ -88,6 +181,36 @@ pub struct GlossaryDB {
     pub revision: i32,
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub domain: String,
+    pub abbreviation: Option<String>,
+    pub expansion: Option<String>,
+    pub status: String,
+    pub status_reason: Option<String>,
+    pub replaced_by: Option<Uuid>,
+    /// Generated from the term and the domain on insert when left empty
+    pub slug: String,
+}
+
+impl Default for GlossaryDB {
+    fn default() -> Self {
+        Self {
+            id: Uuid::default(),
+            term: String::default(),
+            definition: String::default(),
+            revision: 0,
+            created_at: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+            deleted_at: None,
+            domain: String::default(),
+            abbreviation: None,
+            expansion: None,
+            status: GlossaryStatus::Active.as_str().to_string(),
+            status_reason: None,
+            replaced_by: None,
+            slug: String::default(),
+        }
+    }
 }
 
 impl GlossaryDB {
@@ -95,13 +218,30 @@ impl GlossaryDB {
         Glossary {
             id: self.id.to_string(),
             term: self.term.clone(),
+            slug: self.slug.clone(),
+            domain: Some(self.domain.clone()).filter(|d| !d.is_empty()),
+            abbreviation: self.abbreviation.clone(),
+            expansion: self.expansion.clone(),
             definition: self.definition.clone(),
+            locale: None,
+            translation_outdated: false,
+            status: GlossaryStatus::from_str(&self.status).unwrap_or(GlossaryStatus::Active),
+            status_reason: self.status_reason.clone(),
+            replaced_by: self.replaced_by.map(|r| r.to_string()),
             revision: self.revision,
             likes: vec![],
             likes_count: 0,
+            reactions: ReactionCounts::default(),
+            liked_by_me: false,
             who: None,
+            tags: vec![],
+            aliases: vec![],
+            relations: vec![],
+            senses: vec![],
             created_at: Utc.from_utc_datetime(&self.created_at),
             updated_at: Utc.from_utc_datetime(&self.updated_at),
+            deleted_at: self.deleted_at.map(|d| Utc.from_utc_datetime(&d)),
+            warnings: vec![],
         }
     }
 
@@ -112,194 +252,1254 @@ impl GlossaryDB {
         glossary
     }
 
-    pub fn to_glossary_with_who_from_db(&self, conn: &mut PgConnection) -> Glossary {
-        let id = Uuid::from_str(&self.id.to_string()).unwrap();
-        let histories = list_glossary_history(conn, id).unwrap_or_default();
-        let who = histories.last().map(|h| h.who.clone()).unwrap_or_default();
-        self.to_glossary_with_who(who)
+    /// The request which would save the glossary as it is
+    pub fn to_glossary_request(&self) -> GlossaryRequest {
+        GlossaryRequest {
+            term: Some(self.term.clone()),
+            definition: Some(self.definition.clone()),
+            domain: Some(self.domain.clone()).filter(|d| !d.is_empty()),
+            abbreviation: self.abbreviation.clone(),
+            expansion: self.expansion.clone(),
+            status: GlossaryStatus::from_str(&self.status).ok(),
+            status_reason: self.status_reason.clone(),
+            replaced_by: self.replaced_by,
+            revision: None,
+            summary: None,
+        }
+    }
+
+    /// Whether saving `value` with `status_change` would leave the glossary as it is.
+    /// The fields `update_glossary` leaves as they are when not given are unchanged.
+    pub fn is_unchanged(&self, value: &Glossary, status_change: Option<&StatusChange>) -> bool {
+        let unchanged = |current: &Option<String>, new: &Option<String>| match new {
+            Some(new) => current.as_deref().unwrap_or_default() == new,
+            None => true,
+        };
+        let status_unchanged = status_change.is_none_or(|change| {
+            self.status == change.status.as_str()
+                && self.status_reason == change.reason
+                && self.replaced_by == change.replaced_by
+        });
+
+        self.term == value.term
+            && self.definition == value.definition
+            && value.domain.as_ref().is_none_or(|d| *d == self.domain)
+            && unchanged(&self.abbreviation, &value.abbreviation)
+            && unchanged(&self.expansion, &value.expansion)
+            && status_unchanged
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Validate)]
+#[derive(Debug, Default, Deserialize, Serialize, Validate)]
+#[validate(schema(function = "validate_replaced_by"))]
 pub struct GlossaryRequest {
     #[validate(required, length(min = 1, max = 255))]
-    #[serde(deserialize_with = "cleanup_string")]
+    #[serde(deserialize_with = "cleanup_term")]
     pub term: Option<String>,
     #[validate(required)]
     #[serde(deserialize_with = "cleanup_string")]
     pub definition: Option<String>,
+    /// The domain of the sense, e.g. "data" or "ci". Empty for the general sense,
+    /// left as it is on update when not given.
+    #[validate(length(max = 100))]
+    #[serde(
+        default,
+        deserialize_with = "cleanup_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub domain: Option<String>,
+    /// The acronym of the term, or the acronym the term stands for.
+    /// The other side of the pair defaults to the term, an empty string clears it.
+    #[validate(length(max = 50))]
+    #[serde(
+        default,
+        deserialize_with = "cleanup_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub abbreviation: Option<String>,
+    #[validate(length(max = 255))]
+    #[serde(
+        default,
+        deserialize_with = "cleanup_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub expansion: Option<String>,
+    /// Deprecated and banned glossaries need a `status_reason`.
+    /// The status is left as it is on update when not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<GlossaryStatus>,
+    #[serde(
+        default,
+        deserialize_with = "cleanup_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub status_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replaced_by: Option<Uuid>,
+    /// The revision the client last saw. When set (or when `If-Match` is sent),
+    /// the update is rejected with 409 CONFLICT if someone else saved in between.
+    pub revision: Option<i32>,
+    /// Why the edit was made, kept with the revision in the history
+    #[validate(length(max = 500))]
+    #[serde(
+        default,
+        deserialize_with = "cleanup_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub summary: Option<String>,
+}
+
+/// One sense of a term, in a domain
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct Sense {
+    pub id: Uuid,
+    /// `None` for the general sense
+    pub domain: Option<String>,
+    pub definition: String,
+    pub revision: i32,
+}
+
+/// Current state of a glossary returned with a 409 CONFLICT on a stale update
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RevisionConflict {
+    pub revision: i32,
+    pub term: String,
+    pub definition: String,
 }
 
-fn cleanup_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+pub(crate) fn cleanup_string<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let s: &str = Deserialize::deserialize(deserializer)?;
+    // Owned, as a borrowed string can not hold escapes such as `\t`
+    let s: String = Deserialize::deserialize(deserializer)?;
     let s = clean(s.trim());
     Ok(Some(s))
 }
 
+fn cleanup_term<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(cleanup_string(deserializer)?.map(|s| normalize_term(&s)))
+}
+
+/// Terms are compared in NFC with single spaces: "Cafe\u{301}  au lait" is "Café au lait"
+pub fn normalize_term(term: &str) -> String {
+    term.nfc()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The glossary already using a term, returned with a 409 CONFLICT
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TermConflict {
+    pub id: Uuid,
+    pub term: String,
+    pub domain: Option<String>,
+}
+
+/// Fail with a 409 CONFLICT naming the active glossary, other than `except`,
+/// which has the same term in the same domain whatever their case
+fn check_term_available(
+    conn: &mut PgConnection,
+    _term: &str,
+    _domain: &str,
+    except: Option<Uuid>,
+) -> Result<(), ApiError> {
+    let existing = sql_query(
+        "SELECT * FROM glossary
+         WHERE deleted_at IS NULL AND lower(term) = lower($1) AND lower(domain) = lower($2)
+           AND ($3 IS NULL OR id <> $3)
+         LIMIT 1",
+    )
+    .bind::<Text, _>(_term)
+    .bind::<Text, _>(_domain)
+    .bind::<Nullable<diesel::sql_types::Uuid>, _>(except)
+    .get_result::<GlossaryDB>(conn)
+    .optional()?;
+
+    match existing {
+        Some(existing) => Err(ApiError::conflict_with(
+            &format!("\"{}\" already exists as glossary {}", existing.term, existing.id),
+            &TermConflict {
+                id: existing.id,
+                domain: Some(existing.domain).filter(|d| !d.is_empty()),
+                term: existing.term,
+            },
+        )),
+        None => Ok(()),
+    }
+}
+
 impl GlossaryRequest {
     pub fn to_glossary(&self) -> Option<Glossary> {
-        match (&self.term, &self.definition) {
+        let glossary = match (&self.term, &self.definition) {
             (Some(term), Some(definition)) => {
-                Some(Glossary::new(term.to_string(), definition.to_string()))
+                Glossary::new(term.to_string(), definition.to_string())
             }
-            (Some(term), _) => Some(Glossary::new(term.to_string(), "".to_string())),
-            _ => None,
+            (Some(term), _) => Glossary::new(term.to_string(), "".to_string()),
+            _ => return None,
+        };
+
+        let (abbreviation, expansion) = acronym_pair(
+            &glossary.term,
+            self.abbreviation.clone(),
+            self.expansion.clone(),
+        );
+
+        let status = self.status_change().unwrap_or_default();
+
+        Some(Glossary {
+            domain: self.domain.clone(),
+            abbreviation,
+            expansion,
+            status: status.status,
+            status_reason: status.reason,
+            replaced_by: status.replaced_by.map(|r| r.to_string()),
+            ..glossary
+        })
+    }
+
+    /// The status asked for, if any
+    pub fn status_change(&self) -> Option<StatusChange> {
+        self.status.map(|status| StatusChange {
+            status,
+            reason: self.status_reason.clone(),
+            replaced_by: self.replaced_by,
+        })
+    }
+}
+
+/// A replacement comes with the status it replaces the glossary for
+fn validate_replaced_by(request: &GlossaryRequest) -> Result<(), ValidationError> {
+    if request.replaced_by.is_some() && request.status.is_none() {
+        let mut error = ValidationError::new("replaced_by");
+        error.message = Some("replaced_by needs a status".into());
+        return Err(error);
+    }
+
+    Ok(())
+}
+
+/// Whether a glossary is still the one to use
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum GlossaryStatus {
+    #[default]
+    Active,
+    /// Still resolves, but ranked lower in search and left out of popular
+    Deprecated,
+    /// Must not be used anymore
+    Banned,
+}
+
+impl GlossaryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GlossaryStatus::Active => "active",
+            GlossaryStatus::Deprecated => "deprecated",
+            GlossaryStatus::Banned => "banned",
         }
     }
 }
 
-fn list_glossary(conn: &mut PgConnection) -> Result<Vec<GlossaryDB>, Error> {
-    use crate::schema::glossary::dsl::*;
+impl FromStr for GlossaryStatus {
+    type Err = ApiError;
 
-    glossary.order(term.asc()).load(conn)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "active" => Ok(GlossaryStatus::Active),
+            "deprecated" => Ok(GlossaryStatus::Deprecated),
+            "banned" => Ok(GlossaryStatus::Banned),
+            _ => Err(ApiError::internal(&format!("Unknown glossary status: {}", s))),
+        }
+    }
 }
 
-fn search_glossary(conn: &mut PgConnection, query: &str) -> Result<Vec<GlossaryDB>, Error> {
-    use crate::schema::glossary::dsl::*;
+/// A new status for a glossary, with why and what to use instead
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct StatusChange {
+    pub status: GlossaryStatus,
+    pub reason: Option<String>,
+    pub replaced_by: Option<Uuid>,
+}
+
+/// Who edits a glossary and why, kept with the new revision in the history
+#[derive(Debug, Clone, Default)]
+pub struct Edit {
+    pub who: Option<String>,
+    /// The earlier revision the edit reverts the glossary to
+    pub reverted_from: Option<i32>,
+    /// The status is left as it is when not given
+    pub status_change: Option<StatusChange>,
+    pub summary: Option<String>,
+}
+
+/// Check a status change of the glossary `_id` (`None` when creating it).
+/// An active glossary has no reason nor replacement, and the others need a reason.
+fn check_status(
+    conn: &mut PgConnection,
+    _id: Option<Uuid>,
+    change: StatusChange,
+) -> Result<StatusChange, ApiError> {
+    if change.status == GlossaryStatus::Active {
+        return Ok(StatusChange::default());
+    }
+
+    if change.reason.as_deref().unwrap_or_default().is_empty() {
+        return Err(ApiError::invalid_input(&format!(
+            "A {} glossary needs a status_reason",
+            change.status.as_str()
+        )));
+    }
+
+    if let Some(replaced_by) = change.replaced_by {
+        if Some(replaced_by) == _id {
+            return Err(ApiError::invalid_input("A glossary can not replace itself"));
+        }
+        get_glossary(conn, replaced_by)
+            .optional()?
+            .ok_or_else(|| ApiError::invalid_input("The replaced_by glossary does not exist"))?;
+    }
+
+    Ok(change)
+}
+
+/// Complete an abbreviation/expansion pair with the term: given only an expansion,
+/// the term is its abbreviation, and given only an abbreviation, the term is what
+/// it stands for. An empty side is kept to clear it.
+fn acronym_pair(
+    term: &str,
+    abbreviation: Option<String>,
+    expansion: Option<String>,
+) -> (Option<String>, Option<String>) {
+    match (abbreviation, expansion) {
+        (Some(a), None) if !a.is_empty() && !a.eq_ignore_ascii_case(term) => {
+            (Some(a), Some(term.to_string()))
+        }
+        (None, Some(e)) if !e.is_empty() && !e.eq_ignore_ascii_case(term) => {
+            (Some(term.to_string()), Some(e))
+        }
+        pair => pair,
+    }
+}
+
+/// Whether a term is written like an acronym: "API", "CI/CD" or "K8S"
+pub fn looks_like_acronym(term: &str) -> bool {
+    term.chars().filter(|c| c.is_uppercase()).count() >= 2
+        && !term.chars().any(|c| c.is_lowercase() || c.is_whitespace())
+}
+
+/// Default and maximum page size of the list and search APIs
+pub const DEFAULT_PAGE_SIZE: u8 = 50;
+
+/// One page of results, with the total number of results across all pages
+/// and the cursor of the next page if any
+pub struct Page {
+    pub results: Vec<GlossaryDB>,
+    pub total: i64,
+    pub next_cursor: Option<String>,
+}
 
-    let search_pattern = format!("%{}%", query.to_lowercase());
+fn list_glossary(conn: &mut PgConnection) -> Result<Vec<GlossaryDB>, Error> {
+    use crate::schema::glossary::dsl::*;
 
     glossary
-        .filter(
-            term.ilike(&search_pattern)
-                .or(definition.ilike(&search_pattern)),
-        )
+        .filter(deleted_at.is_null())
         .order(term.asc())
         .load(conn)
 }
 
+/// Separates the term from the domain in a list cursor
+const CURSOR_DOMAIN_SEPARATOR: char = '\u{1f}';
+
+/// List glossaries ordered by term then domain, starting after the sense `after`
+fn list_glossary_page(
+    conn: &mut PgConnection,
+    after: Option<String>,
+    limit: u8,
+) -> Result<Page, Error> {
+    use crate::schema::glossary::dsl::*;
+
+    let total = glossary
+        .filter(deleted_at.is_null())
+        .count()
+        .get_result::<i64>(conn)?;
+
+    let mut query = glossary.filter(deleted_at.is_null()).into_boxed();
+    if let Some(after) = after {
+        let (after_term, after_domain) = after
+            .split_once(CURSOR_DOMAIN_SEPARATOR)
+            .map(|(t, d)| (t.to_string(), d.to_string()))
+            .unwrap_or((after, String::new()));
+        query = query.filter(
+            term.gt(after_term.clone())
+                .or(term.eq(after_term).and(domain.gt(after_domain))),
+        );
+    }
+
+    // Fetch one more row to know whether there is a next page
+    let mut results = query
+        .order((term.asc(), domain.asc()))
+        .limit(limit as i64 + 1)
+        .load::<GlossaryDB>(conn)?;
+    let has_next = results.len() > limit as usize;
+    results.truncate(limit as usize);
+
+    let next_cursor = match (has_next, results.last()) {
+        (true, Some(last)) => Some(encode_cursor(&format!(
+            "{}{}{}",
+            last.term, CURSOR_DOMAIN_SEPARATOR, last.domain
+        ))),
+        _ => None,
+    };
+
+    Ok(Page {
+        results,
+        total,
+        next_cursor,
+    })
+}
+
+#[derive(QueryableByName)]
+struct SearchRow {
+    #[diesel(embed)]
+    glossary: GlossaryDB,
+    #[diesel(sql_type = Float)]
+    rank: f32,
+}
+
+#[derive(QueryableByName)]
+struct SearchCount {
+    #[diesel(sql_type = BigInt)]
+    total: i64,
+}
+
+/// Full-text search on term and definition, ranked by relevance.
+/// Deprecated and banned glossaries rank lower.
+/// The query uses the `websearch_to_tsquery` syntax: `"quoted phrase"`, `-exclusion`, `or`.
+/// An acronym also matches its expansion, and an expansion its acronym.
+/// The cursor is the `(rank, term, domain)` of the last result of the previous page.
+fn search_glossary(
+    conn: &mut PgConnection,
+    query: &str,
+    after: Option<String>,
+    limit: u8,
+) -> Result<Page, Error> {
+    let (after_rank, after_term, after_domain) =
+        match after.as_deref().and_then(|a| a.split_once(':')) {
+            Some((rank, sense)) => {
+                let (sense_term, sense_domain) = sense
+                    .split_once(CURSOR_DOMAIN_SEPARATOR)
+                    .unwrap_or((sense, ""));
+                (
+                    rank.parse::<f32>().ok(),
+                    Some(sense_term.to_string()),
+                    Some(sense_domain.to_string()),
+                )
+            }
+            None => (None, None, None),
+        };
+
+    let total = sql_query(
+        "SELECT count(*) AS total
+         FROM glossary, glossary_search_query($1) AS query
+         WHERE deleted_at IS NULL AND search_vector @@ query",
+    )
+    .bind::<Text, _>(query)
+    .get_result::<SearchCount>(conn)?
+    .total;
+
+    // Fetch one more row to know whether there is a next page
+    let mut rows = sql_query(
+        "SELECT glossary.*, ts_rank(search_vector, query) * glossary_status_weight(status) AS rank
+         FROM glossary, glossary_search_query($1) AS query
+         WHERE deleted_at IS NULL AND search_vector @@ query
+           AND ($2 IS NULL
+                OR ts_rank(search_vector, query) * glossary_status_weight(status) < $2
+                OR (ts_rank(search_vector, query) * glossary_status_weight(status) = $2
+                    AND (term > $3 OR (term = $3 AND domain > $4))))
+         ORDER BY rank DESC, term ASC, domain ASC
+         LIMIT $5",
+    )
+    .bind::<Text, _>(query)
+    .bind::<Nullable<Float>, _>(after_rank)
+    .bind::<Nullable<Text>, _>(after_term)
+    .bind::<Nullable<Text>, _>(after_domain)
+    .bind::<BigInt, _>(limit as i64 + 1)
+    .load::<SearchRow>(conn)?;
+
+    let has_next = rows.len() > limit as usize;
+    rows.truncate(limit as usize);
+
+    let next_cursor = match (has_next, rows.last()) {
+        (true, Some(last)) => Some(encode_cursor(&format!(
+            "{}:{}{}{}",
+            last.rank, last.glossary.term, CURSOR_DOMAIN_SEPARATOR, last.glossary.domain
+        ))),
+        _ => None,
+    };
+
+    Ok(Page {
+        results: rows.into_iter().map(|row| row.glossary).collect(),
+        total,
+        next_cursor,
+    })
+}
+
+/// A lightweight glossary for autocomplete
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq, QueryableByName)]
+pub struct Suggestion {
+    #[diesel(sql_type = diesel::sql_types::Uuid)]
+    pub id: Uuid,
+    #[diesel(sql_type = Text)]
+    pub term: String,
+    /// Tells the senses of a term apart, `None` for the general sense
+    #[diesel(sql_type = Nullable<Text>)]
+    pub domain: Option<String>,
+}
+
+/// Escape the `LIKE` wildcards of a user input
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Glossaries whose term or one of its aliases starts with `prefix` (case-insensitive),
+/// most liked first
+fn suggest_glossary(
+    conn: &mut PgConnection,
+    prefix: &str,
+    limit: u8,
+) -> Result<Vec<Suggestion>, Error> {
+    sql_query(
+        "SELECT glossary.id, glossary.term, NULLIF(glossary.domain, '') AS domain
+         FROM glossary
+         LEFT JOIN likes ON likes.glossary_id = glossary.id AND likes.kind = 'helpful'
+         WHERE glossary.deleted_at IS NULL
+           AND (lower(glossary.term) LIKE $1
+                OR EXISTS (SELECT 1 FROM glossary_aliases
+                           WHERE glossary_aliases.glossary_id = glossary.id
+                             AND lower(glossary_aliases.alias) LIKE $1))
+         GROUP BY glossary.id
+         ORDER BY count(likes.id) DESC, glossary.term ASC
+         LIMIT $2",
+    )
+    .bind::<Text, _>(format!("{}%", escape_like(&prefix.to_lowercase())))
+    .bind::<BigInt, _>(limit as i64)
+    .load::<Suggestion>(conn)
+}
+
 fn create_glossary(
     conn: &mut PgConnection,
     value: Json<GlossaryRequest>,
     who: Option<String>,
-) -> Result<GlossaryDB, Error> {
+) -> Result<GlossaryDB, ApiError> {
     use crate::schema::glossary::dsl::*;
 
-    let _glossary = value.into_inner().to_glossary().unwrap();
-
-    let created = diesel::insert_into(glossary)
-        .values(_glossary.to_glossary_db())
-        .returning((id, term, definition, revision, created_at, updated_at))
-        .get_result::<GlossaryDB>(conn)?;
-
-    create_glossary_history(
+    let value = value.into_inner();
+    let new_status = check_status(conn, None, value.status_change().unwrap_or_default())?;
+    let _glossary = Glossary {
+        status: new_status.status,
+        status_reason: new_status.reason,
+        replaced_by: new_status.replaced_by.map(|r| r.to_string()),
+        ..value.to_glossary().unwrap()
+    };
+    check_term_available(
         conn,
-        created.term.to_string(),
-        created.definition.to_string(),
-        who,
-        created.revision,
-        created.id,
-    );
+        &_glossary.term,
+        _glossary.domain.as_deref().unwrap_or_default(),
+        None,
+    )?;
+
+    // The glossary is only saved with its first history revision
+    let created = conn.transaction::<_, Error, _>(|conn| {
+        let created = diesel::insert_into(glossary)
+            .values(_glossary.to_glossary_db())
+            .get_result::<GlossaryDB>(conn)?;
+
+        create_glossary_history(
+            conn,
+            &created,
+            who,
+            HistoryAction::Create,
+            None,
+            value.summary,
+        )?;
+
+        Ok(created)
+    })?;
 
     Ok(created)
 }
 
-fn get_glossary(conn: &mut PgConnection, _id: Uuid) -> Result<GlossaryDB, Error> {
+/// Find a glossary by id, glossaries in the trash are not found
+pub fn get_glossary(conn: &mut PgConnection, _id: Uuid) -> Result<GlossaryDB, Error> {
+    use crate::schema::glossary::dsl::*;
+
+    glossary
+        .filter(id.eq(_id))
+        .filter(deleted_at.is_null())
+        .first::<GlossaryDB>(conn)
+}
+
+/// Every sense of a term whatever its case, the general sense first
+pub fn list_senses(conn: &mut PgConnection, _term: &str) -> Result<Vec<Sense>, Error> {
+    use crate::schema::glossary::dsl::*;
+
+    let senses = glossary
+        .filter(lower(term).eq(lower(_term)))
+        .filter(deleted_at.is_null())
+        .select((id, domain, definition, revision))
+        .order(domain.asc())
+        .load::<(Uuid, String, String, i32)>(conn)?;
+
+    Ok(senses
+        .into_iter()
+        .map(|(_id, _domain, _definition, _revision)| Sense {
+            id: _id,
+            domain: Some(_domain).filter(|d| !d.is_empty()),
+            definition: _definition,
+            revision: _revision,
+        })
+        .collect())
+}
+
+/// Find a glossary by its term or one of its aliases, whatever their case.
+/// The exact term wins over a case-insensitive match, and the general sense
+/// over the other senses of the term.
+pub fn get_glossary_by_term(conn: &mut PgConnection, name: &str) -> Result<GlossaryDB, Error> {
+    sql_query(
+        "SELECT glossary.*
+         FROM glossary
+         LEFT JOIN glossary_aliases ON glossary_aliases.glossary_id = glossary.id
+         WHERE glossary.deleted_at IS NULL
+           AND (lower(glossary.term) = lower($1) OR lower(glossary_aliases.alias) = lower($1))
+         ORDER BY glossary.term = $1 DESC, lower(glossary.term) = lower($1) DESC,
+                  glossary.domain = '' DESC, glossary.domain ASC
+         LIMIT 1",
+    )
+    .bind::<Text, _>(normalize_term(name))
+    .get_result::<GlossaryDB>(conn)
+}
+
+/// Find a glossary by its current slug
+pub fn get_glossary_by_slug(conn: &mut PgConnection, _slug: &str) -> Result<GlossaryDB, Error> {
     use crate::schema::glossary::dsl::*;
 
-    glossary.filter(id.eq(_id)).first::<GlossaryDB>(conn)
+    glossary
+        .filter(slug.eq(_slug))
+        .filter(deleted_at.is_null())
+        .first::<GlossaryDB>(conn)
+}
+
+/// The current slug of the glossary an earlier slug redirects to, if any
+pub fn find_slug_redirect(
+    conn: &mut PgConnection,
+    old_slug: &str,
+) -> Result<Option<String>, Error> {
+    glossary_slug_redirects::table
+        .inner_join(glossary::table)
+        .filter(glossary_slug_redirects::slug.eq(old_slug))
+        .filter(glossary::deleted_at.is_null())
+        .select(glossary::slug)
+        .first::<String>(conn)
+        .optional()
+}
+
+/// The current slug of the glossary an earlier term redirects to, if any
+pub fn find_term_redirect(conn: &mut PgConnection, name: &str) -> Result<Option<String>, Error> {
+    glossary_term_redirects::table
+        .inner_join(glossary::table)
+        .filter(lower(glossary_term_redirects::term).eq(lower(normalize_term(name))))
+        .filter(glossary::deleted_at.is_null())
+        .select(glossary::slug)
+        .first::<String>(conn)
+        .optional()
 }
 
+/// Update a glossary, only if its revision still is `expected_revision`.
+/// Without an expected revision, the update applies on top of the current one.
+/// The history revision is saved in the same transaction, `edit.reverted_from`
+/// marks it as a revert of an earlier one. An update which changes nothing returns
+/// the glossary as it is, without a new revision.
 fn update_glossary(
     conn: &mut PgConnection,
     _id: Uuid,
     value: Glossary,
-    who: Option<String>,
-) -> Result<GlossaryDB, Error> {
+    expected_revision: Option<i32>,
+    edit: Edit,
+) -> Result<GlossaryDB, ApiError> {
     use crate::schema::glossary::dsl::*;
 
-    let updated = diesel::update(glossary.find(_id))
-        .set((
-            term.eq(value.term),
-            definition.eq(value.definition),
-            revision.eq(revision + 1),
-            updated_at.eq(Utc::now().naive_utc()),
-        ))
-        .returning((id, term, definition, revision, created_at, updated_at))
-        .get_result::<GlossaryDB>(conn)?;
-
-    create_glossary_history(
+    conn.transaction::<_, ApiError, _>(|conn| {
+        let current = get_glossary(conn, _id)?;
+        let expected_revision = expected_revision.unwrap_or(current.revision);
+        // The status is left as it is when not given
+        let new_status = match edit.status_change {
+            Some(change) => check_status(conn, Some(_id), change)?,
+            None => StatusChange {
+                status: GlossaryStatus::from_str(&current.status)?,
+                reason: current.status_reason.clone(),
+                replaced_by: current.replaced_by,
+            },
+        };
+        if expected_revision == current.revision
+            && current.is_unchanged(&value, Some(&new_status))
+        {
+            return Ok(current);
+        }
+        // A side of the acronym pair which is not given is left as it is
+        let new_abbreviation = value.abbreviation.or(current.abbreviation);
+        let new_expansion = value.expansion.or(current.expansion);
+        let new_domain = value.domain.unwrap_or(current.domain);
+        check_term_available(conn, &value.term, &new_domain, Some(_id))?;
+
+        let target = glossary
+            .find(_id)
+            .filter(revision.eq(expected_revision))
+            .filter(deleted_at.is_null());
+        let updated = diesel::update(target)
+            .set((
+                term.eq(value.term),
+                domain.eq(new_domain),
+                abbreviation.eq(new_abbreviation.filter(|a| !a.is_empty())),
+                expansion.eq(new_expansion.filter(|e| !e.is_empty())),
+                status.eq(new_status.status.as_str()),
+                status_reason.eq(new_status.reason),
+                replaced_by.eq(new_status.replaced_by),
+                definition.eq(value.definition),
+                revision.eq(revision + 1),
+                updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .get_result::<GlossaryDB>(conn)
+            .optional()?;
+
+        match updated {
+            Some(updated) => {
+                if updated.definition != current.definition {
+                    mark_translations_outdated(conn, _id)?;
+                }
+                let action = match edit.reverted_from {
+                    Some(_) => HistoryAction::Revert,
+                    None => HistoryAction::Update,
+                };
+                create_glossary_history(
+                    conn,
+                    &updated,
+                    edit.who,
+                    action,
+                    edit.reverted_from,
+                    edit.summary,
+                )?;
+                Ok(updated)
+            }
+            None => {
+                // Someone else saved in between, re-read what they saved
+                let current = get_glossary(conn, _id)?;
+                Err(ApiError::conflict_with(
+                    &format!(
+                        "Glossary has been modified: expected revision {}, current revision is {}",
+                        expected_revision, current.revision
+                    ),
+                    &RevisionConflict {
+                        revision: current.revision,
+                        term: current.term,
+                        definition: current.definition,
+                    },
+                ))
+            }
+        }
+    })
+}
+
+/// Apply a JSON Merge Patch (RFC 7396) to `target`: the members of an object
+/// patch are merged recursively, a `null` member removes the target member and
+/// anything else replaces the target.
+fn merge_patch(target: &mut serde_json::Value, _patch: &serde_json::Value) {
+    let members = match _patch {
+        serde_json::Value::Object(members) => members,
+        _ => {
+            *target = _patch.clone();
+            return;
+        }
+    };
+
+    if !target.is_object() {
+        *target = serde_json::Value::Object(serde_json::Map::new());
+    }
+    if let serde_json::Value::Object(target) = target {
+        for (key, value) in members {
+            if value.is_null() {
+                target.remove(key);
+            } else {
+                merge_patch(target.entry(key.as_str()).or_insert(serde_json::Value::Null), value);
+            }
+        }
+    }
+}
+
+/// Apply a JSON Merge Patch to the request which would save the glossary as it
+/// is, then save the result like a `PUT`.
+/// The patch applies to the revision read here unless another one is expected.
+fn patch_glossary(
+    conn: &mut PgConnection,
+    _id: Uuid,
+    _patch: serde_json::Value,
+    expected_revision: Option<i32>,
+    who: Option<String>,
+) -> Result<GlossaryDB, ApiError> {
+    let current = get_glossary(conn, _id)?;
+
+    let mut value = serde_json::to_value(current.to_glossary_request())
+        .map_err(|e| ApiError::internal(&e.to_string()))?;
+    merge_patch(&mut value, &_patch);
+    let patched: GlossaryRequest = serde_json::from_value(value)
+        .map_err(|e| ApiError::UnprocessableEntity(e.to_string()))?;
+    patched
+        .validate()
+        .map_err(|e| ApiError::invalid_input(&e.to_string()))?;
+
+    // A field removed by the patch is cleared, rather than left as it is
+    let glossary = patched.to_glossary().unwrap();
+    let glossary = Glossary {
+        domain: Some(glossary.domain.unwrap_or_default()),
+        abbreviation: Some(glossary.abbreviation.unwrap_or_default()),
+        expansion: Some(glossary.expansion.unwrap_or_default()),
+        ..glossary
+    };
+    let status_change = patched.status_change().unwrap_or_default();
+    let expected_revision = patched.revision.or(expected_revision);
+
+    update_glossary(
         conn,
-        updated.term.to_string(),
-        updated.definition.to_string(),
-        who,
-        updated.revision,
-        updated.id,
-    );
+        _id,
+        glossary,
+        Some(expected_revision.unwrap_or(current.revision)),
+        Edit {
+            who,
+            status_change: Some(status_change),
+            summary: patched.summary,
+            ..Default::default()
+        },
+    )
+}
+
+fn revision_etag(revision: i32) -> ETag {
+    ETag(EntityTag::new_strong(revision.to_string()))
+}
+
+/// The ETag of a glossary as served. A translation gets a weak ETag of its own,
+/// which `If-Match` refuses, so it is never saved over the source language.
+fn glossary_etag(glossary: &Glossary) -> ETag {
+    match &glossary.locale {
+        Some(locale) => ETag(EntityTag::new_weak(format!("{}-{}", glossary.revision, locale))),
+        None => revision_etag(glossary.revision),
+    }
+}
+
+/// Parse the revision from an `If-Match` header carrying an ETag from `revision_etag`.
+/// `If-Match: *` does not expect any revision.
+fn parse_if_match(req: &HttpRequest) -> Result<Option<i32>, ApiError> {
+    let value = match req.headers().get(header::IF_MATCH) {
+        Some(value) => value
+            .to_str()
+            .map_err(|_| ApiError::invalid_input("Invalid If-Match header"))?
+            .trim(),
+        None => return Ok(None),
+    };
+
+    if value == "*" {
+        return Ok(None);
+    }
+
+    if value.starts_with("W/") {
+        return Err(ApiError::invalid_input(
+            "If-Match needs the ETag of the glossary in its source language",
+        ));
+    }
 
-    Ok(updated)
+    value
+        .trim_matches('"')
+        .parse::<i32>()
+        .map(Some)
+        .map_err(|_| ApiError::invalid_input("Invalid If-Match header"))
 }
 
-fn delete_glossary(conn: &mut PgConnection, _id: Uuid) -> Result<usize, Error> {
+fn delete_glossary(
+    conn: &mut PgConnection,
+    _id: Uuid,
+    who: Option<String>,
+) -> Result<usize, Error> {
     use crate::schema::glossary::dsl::*;
 
-    // Database CASCADE constraints handle deletion of dependent records
-    // (glossary_history, likes) automatically
-    diesel::delete(glossary.find(_id)).execute(conn)
+    conn.transaction(|conn| {
+        // Move the glossary to the trash, likes and history are kept
+        // until it is purged from the trash
+        let deleted = diesel::update(glossary.find(_id).filter(deleted_at.is_null()))
+            .set((
+                deleted_at.eq(Utc::now().naive_utc()),
+                revision.eq(revision + 1),
+            ))
+            .get_result::<GlossaryDB>(conn)
+            .optional()?;
+
+        let deleted = match deleted {
+            Some(deleted) => deleted,
+            None => return Ok(0),
+        };
+
+        create_glossary_history(
+            conn,
+            &deleted,
+            who.clone(),
+            HistoryAction::Delete,
+            None,
+            None,
+        )?;
+
+        // The relations are kept, so they are back when the glossary is restored.
+        // Relations to a glossary in the trash are not listed.
+
+        // The tombstone outlives the glossary, so we know who deleted it
+        create_tombstone(conn, &deleted, who)
+    })
 }
 
-fn list_popular_glossary(
+/// Merge a glossary into `target`. Its likes, aliases, tags, translations, relations
+/// and history move over, then it goes to the trash and its term and slug redirect
+/// to the target.
+fn merge_glossary(
     conn: &mut PgConnection,
-    limit: Option<u8>,
-) -> Result<Vec<Glossary>, Error> {
-    use diesel::dsl;
-
-    let limit = limit.unwrap_or(10);
-
-    // Most likes glossaries
-    let most_glossary_id_by_count = likes::table
-        .select(likes::columns::glossary_id)
-        .order(dsl::count_star().desc())
-        .group_by(likes::columns::glossary_id)
-        .limit(limit as i64)
-        .load::<Uuid>(conn)?;
-
-    // Get glossaries in the list
-    let glossaries = glossary::table
-        .filter(glossary::columns::id.eq_any(most_glossary_id_by_count))
-        .load::<GlossaryDB>(conn)
-        .unwrap()
-        .into_iter()
-        .map(|a| a.to_glossary())
-        .collect();
+    _id: Uuid,
+    target: Uuid,
+    who: Option<String>,
+) -> Result<GlossaryDB, ApiError> {
+    use crate::schema::glossary::dsl::*;
+
+    if _id == target {
+        return Err(ApiError::invalid_input(
+            "A glossary can not be merged into itself",
+        ));
+    }
+
+    conn.transaction::<_, ApiError, _>(|conn| {
+        let merged = get_glossary(conn, _id)?;
+        get_glossary(conn, target)?;
+
+        // A like moves over unless its author already reacted the same way to the target
+        sql_query(
+            "UPDATE likes SET glossary_id = $2
+             WHERE glossary_id = $1
+               AND NOT EXISTS (SELECT 1 FROM likes AS kept
+                               WHERE kept.glossary_id = $2
+                                 AND kept.who = likes.who AND kept.kind = likes.kind)",
+        )
+        .bind::<diesel::sql_types::Uuid, _>(_id)
+        .bind::<diesel::sql_types::Uuid, _>(target)
+        .execute(conn)?;
+        sql_query(
+            "INSERT INTO glossary_tags (glossary_id, tag_id)
+             SELECT $2, tag_id FROM glossary_tags WHERE glossary_id = $1
+             ON CONFLICT DO NOTHING",
+        )
+        .bind::<diesel::sql_types::Uuid, _>(_id)
+        .bind::<diesel::sql_types::Uuid, _>(target)
+        .execute(conn)?;
+        diesel::update(glossary_aliases::table.filter(glossary_aliases::glossary_id.eq(_id)))
+            .set(glossary_aliases::glossary_id.eq(target))
+            .execute(conn)?;
+        diesel::update(
+            glossary_term_redirects::table.filter(glossary_term_redirects::glossary_id.eq(_id)),
+        )
+        .set(glossary_term_redirects::glossary_id.eq(target))
+        .execute(conn)?;
+        diesel::update(
+            glossary_slug_redirects::table.filter(glossary_slug_redirects::glossary_id.eq(_id)),
+        )
+        .set(glossary_slug_redirects::glossary_id.eq(target))
+        .execute(conn)?;
+        diesel::update(glossary.filter(replaced_by.eq(_id)).filter(id.ne(target)))
+            .set(replaced_by.eq(target))
+            .execute(conn)?;
+        move_translations(conn, _id, target)?;
+        move_relations(conn, _id, target)?;
+
+        // The merge is a new revision of the target, the history of the
+        // merged glossary is copied along
+        let saved = diesel::update(glossary.find(target))
+            .set((
+                revision.eq(revision + 1),
+                updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .get_result::<GlossaryDB>(conn)?;
+        merge_glossary_history(conn, &saved, _id, who.clone())?;
+
+        // The merged glossary goes to the trash, with its own history
+        delete_glossary(conn, _id, who)?;
+
+        // The merged term redirects unless another sense still uses it
+        sql_query(
+            "INSERT INTO glossary_term_redirects (id, glossary_id, term)
+             SELECT $1, $2, $3
+             WHERE NOT EXISTS (SELECT 1 FROM glossary
+                               WHERE deleted_at IS NULL AND lower(term) = lower($3))
+             ON CONFLICT (lower(term)) DO UPDATE SET glossary_id = EXCLUDED.glossary_id",
+        )
+        .bind::<diesel::sql_types::Uuid, _>(Uuid::new_v4())
+        .bind::<diesel::sql_types::Uuid, _>(target)
+        .bind::<Text, _>(&merged.term)
+        .execute(conn)?;
+        diesel::insert_into(glossary_slug_redirects::table)
+            .values((
+                glossary_slug_redirects::slug.eq(&merged.slug),
+                glossary_slug_redirects::glossary_id.eq(target),
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn)?;
+
+        Ok(saved)
+    })
+}
 
-    Ok(glossaries)
+/// Likes only count for half as much every `TRENDING_HALF_LIFE_DAYS` days in trending mode
+pub const TRENDING_HALF_LIFE_DAYS: f64 = 7.0;
+
+/// The likes taken into account by the popularity ranking
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopularWindow {
+    Week,
+    Month,
+    All,
 }
 
-pub type GroupedGlossary = std::collections::HashMap<String, Vec<Glossary>>;
+impl PopularWindow {
+    /// The oldest like in the window, `None` for all likes
+    pub fn since(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        match self {
+            PopularWindow::Week => Some(now - chrono::Duration::days(7)),
+            PopularWindow::Month => Some(now - chrono::Duration::days(30)),
+            PopularWindow::All => None,
+        }
+    }
+}
 
-/// List all glossaries
-#[get("/glossary")]
-pub async fn list(pool: web::Data<DBPool>) -> actix_web::Result<impl Responder, ApiError> {
-    let mut conn = pool.get().expect("could not get db connection from pool");
+impl FromStr for PopularWindow {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "7d" => Ok(PopularWindow::Week),
+            "30d" => Ok(PopularWindow::Month),
+            "all" => Ok(PopularWindow::All),
+            _ => Err(ApiError::invalid_input("Window must be one of 7d, 30d, all")),
+        }
+    }
+}
+
+/// How the popularity score of a glossary is computed from its likes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopularMode {
+    /// Number of likes
+    Top,
+    /// Likes decayed by their age, recent likes weigh more
+    Trending,
+}
+
+impl FromStr for PopularMode {
+    type Err = ApiError;
 
-    // Diesel does not support tokio (the asynchronous engine behind Actix),
-    // so we have to run it in separate threads using the web::block
-    let glossaries = web::block(move || list_glossary(&mut conn)).await??;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "top" => Ok(PopularMode::Top),
+            "trending" => Ok(PopularMode::Trending),
+            _ => Err(ApiError::invalid_input("Mode must be one of top, trending")),
+        }
+    }
+}
+
+/// Glossaries with the most reactions of `kind` first, likes (`Helpful`) for the popular ones.
+/// Only the reactions in `window` count towards the ranking, and only active glossaries.
+pub fn list_popular_glossary(
+    conn: &mut PgConnection,
+    limit: u8,
+    window: PopularWindow,
+    mode: PopularMode,
+    kind: ReactionKind,
+    me: Option<&str>,
+) -> Result<Vec<Glossary>, Error> {
+    let score = match mode {
+        PopularMode::Top => "count(likes.id)::float8",
+        PopularMode::Trending => {
+            "sum(power(0.5, extract(epoch FROM ($2 - likes.created_at)) / 86400 / $3))::float8"
+        }
+    };
+    let now = Utc::now().naive_utc();
+
+    let rows = sql_query(format!(
+        "SELECT glossary.*, {} AS score
+         FROM glossary
+         INNER JOIN likes ON likes.glossary_id = glossary.id
+         WHERE glossary.deleted_at IS NULL AND glossary.status = 'active' AND likes.kind = $5
+           AND ($1 IS NULL OR likes.created_at >= $1)
+         GROUP BY glossary.id
+         ORDER BY score DESC, glossary.term ASC
+         LIMIT $4",
+        score
+    ))
+    .bind::<Nullable<Timestamp>, _>(window.since(now))
+    .bind::<Timestamp, _>(now)
+    .bind::<Double, _>(TRENDING_HALF_LIFE_DAYS)
+    .bind::<BigInt, _>(limit as i64)
+    .bind::<Text, _>(kind.as_str())
+    .load::<GlossaryDB>(conn)?;
+
+    to_glossaries(conn, rows, me)
+}
 
+pub type GroupedGlossary = std::collections::HashMap<String, Vec<Glossary>>;
+
+/// Load the reactions counts, the last editor, the tags and the aliases of glossaries,
+/// and whether `me` likes them.
+/// This runs the same number of queries however many glossaries there are.
+pub fn to_glossaries(
+    conn: &mut PgConnection,
+    rows: Vec<GlossaryDB>,
+    me: Option<&str>,
+) -> Result<Vec<Glossary>, Error> {
+    let ids: Vec<Uuid> = rows.iter().map(|a| a.id).collect();
+    let mut reactions_by_glossary = count_reactions_by_glossary(conn, &ids)?;
+    let liked_by_me = match me {
+        Some(me) => list_liked_by(conn, &ids, me)?,
+        None => Default::default(),
+    };
+    let mut editors_by_glossary = list_last_editors(conn, &ids)?;
+    let mut tags_by_glossary = list_tag_names_by_glossary(conn, &ids)?;
+    let mut aliases_by_glossary = list_aliases_by_glossary(conn, &ids)?;
+
+    Ok(rows
+        .into_iter()
+        .map(|a| {
+            let who = editors_by_glossary.remove(&a.id).flatten();
+            let tags = tags_by_glossary.remove(&a.id).unwrap_or_default();
+            let mut glossary = a.to_glossary_with_who(who).add_tags(tags);
+            glossary.aliases = aliases_by_glossary.remove(&a.id).unwrap_or_default();
+            glossary.reactions = reactions_by_glossary.remove(&a.id).unwrap_or_default();
+            glossary.likes_count = glossary.reactions.helpful;
+            glossary.liked_by_me = liked_by_me.contains(&a.id);
+            glossary
+        })
+        .collect())
+}
+
+/// Group glossaries by the first character of their term
+fn group_by_alphabet(glossaries: Vec<Glossary>) -> GroupedGlossary {
     let mut glossaries_by_alphabet: HashMap<String, Vec<Glossary>> = HashMap::new();
-    let mut conn = pool.get().expect("could not get db connection from pool");
 
     glossaries.into_iter().for_each(|a| {
-        let id = Uuid::from_str(&a.id.to_string()).unwrap();
-        let likes = list_likes(&mut conn, id).unwrap_or_default();
-        let histories = list_glossary_history(&mut conn, id).unwrap_or_default();
-        let who = match histories.last() {
-            Some(h) => h.who.clone().unwrap_or_default(),
-            None => "".to_string(),
-        };
-
         let character = a.term.chars().next().unwrap().to_uppercase();
         let b = glossaries_by_alphabet
             .entry(character.to_string())
-            .or_insert_with(Vec::new);
-        b.push(a.to_glossary().add_likes(likes).add_who(who));
+            .or_default();
+        b.push(a);
     });
 
-    Ok(web::Json(glossaries_by_alphabet as GroupedGlossary))
+    glossaries_by_alphabet
+}
+
+/// Turn a page of glossaries into a response, with a link to the next page.
+/// `next_link` builds the link from the cursor of the next page.
+fn to_page_resp(
+    conn: &mut PgConnection,
+    page: Page,
+    me: Option<&str>,
+    locales: &[String],
+    next_link: impl Fn(&str) -> String,
+) -> Result<Glossaries, Error> {
+    let glossaries = to_glossaries(conn, page.results, me)?;
+    let glossaries = translate_glossaries(conn, glossaries, locales)?;
+    let resp = Glossaries::from(&glossaries).with_total(page.total);
+
+    Ok(match page.next_cursor {
+        Some(cursor) => {
+            let link = next_link(&cursor);
+            resp.with_next(cursor, link)
+        }
+        None => resp,
+    })
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct ListQuery {
+    /// `grouped` to list all glossaries grouped by their first character
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub view: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+}
+
+/// List glossaries ordered by term, `/glossary?limit=50&cursor=...`.
+/// `/glossary?view=grouped` lists all glossaries grouped by their first character.
+/// `?lang=` or `Accept-Language` serves the translations, if any.
+#[get("/glossary")]
+pub async fn list(
+    pool: web::Data<DBPool>,
+    query: web::Query<ListQuery>,
+    req: HttpRequest,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let me = req
+        .headers()
+        .get(crate::AUTHENTICATED_USER_HEADER)
+        .map(|email| email.to_str().unwrap().to_string());
+    let locales = preferred_locales(query.lang.as_deref(), &req)?;
+
+    if query.view.as_deref() == Some("grouped") {
+        // Diesel does not support tokio (the asynchronous engine behind Actix),
+        // so we have to run it in separate threads using the web::block
+        let glossaries = web::block(move || {
+            let glossaries = list_glossary(&mut conn)?;
+            let glossaries = to_glossaries(&mut conn, glossaries, me.as_deref())?;
+            translate_glossaries(&mut conn, glossaries, &locales)
+        })
+        .await??;
+
+        return Ok(HttpResponse::Ok()
+            .insert_header((header::VARY, "Accept-Language"))
+            .json(group_by_alphabet(glossaries)));
+    }
+
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, DEFAULT_PAGE_SIZE);
+    let after = query.cursor.as_deref().map(decode_cursor).transpose()?;
+    let path = req.path().to_string();
+    let lang = query.lang.clone();
+
+    let resp = web::block(move || {
+        let page = list_glossary_page(&mut conn, after, limit)?;
+        to_page_resp(&mut conn, page, me.as_deref(), &locales, |cursor| {
+            let next = ListQuery {
+                limit: Some(limit),
+                cursor: Some(cursor.to_string()),
+                lang: lang.clone(),
+                ..Default::default()
+            };
+            format!("{}?{}", path, serde_urlencoded::to_string(next).unwrap())
+        })
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok()
+        .insert_header((header::VARY, "Accept-Language"))
+        .json(resp))
 }
 
 /// Create a new glossary
@@ -317,27 +1517,175 @@ pub async fn create(
         .map(|email| email.to_str().unwrap().to_string());
     let who_ = who.clone();
 
-    let result = web::block(move || create_glossary(&mut conn, json, who)).await??;
-    Ok(web::Json(result.to_glossary_with_who(who_)))
+    let (result, senses) = web::block(move || {
+        let created = create_glossary(&mut conn, json, who)?;
+        let senses = list_senses(&mut conn, &created.term)?;
+        Ok::<_, ApiError>((created, senses))
+    })
+    .await??;
+
+    let mut warnings = vec![];
+    if looks_like_acronym(&result.term) && result.expansion.is_none() {
+        warnings.push(format!("{} looks like an acronym, add its expansion", result.term));
+    }
+    Ok(HttpResponse::Ok()
+        .insert_header(revision_etag(result.revision))
+        .json(Glossary {
+            senses,
+            warnings,
+            ..result.to_glossary_with_who(who_)
+        }))
 }
 
-/// Find a glossary by id
+/// Find a glossary by id, `?lang=` or `Accept-Language` serves a translation if any
 #[get("/glossary/{id}")]
 pub async fn get(
     pool: web::Data<DBPool>,
     id: web::Path<String>,
+    query: web::Query<LangQuery>,
+    req: HttpRequest,
 ) -> actix_web::Result<impl Responder, ApiError> {
     let mut conn = pool.get().expect("could not get db connection from pool");
-    let mut conn2 = pool.get().expect("could not get db connection from pool");
-
-    let glossary_id = Uuid::from_str(&id)
+    let me = req
+        .headers()
+        .get(crate::AUTHENTICATED_USER_HEADER)
+        .map(|email| email.to_str().unwrap().to_string());
+
+    let glossary_id = Uuid::from_str(&id)
         .map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+    let locales = preferred_locales(query.lang.as_deref(), &req)?;
+
+    let glossary = web::block(move || {
+        let found = get_glossary(&mut conn, glossary_id)?;
+        load_glossary(&mut conn, found, me.as_deref(), &locales)
+    })
+    .await??;
+
+    Ok(glossary_resp(glossary))
+}
+
+/// A single glossary with its likes, relations and senses, translated if asked for
+fn load_glossary(
+    conn: &mut PgConnection,
+    found: GlossaryDB,
+    me: Option<&str>,
+    locales: &[String],
+) -> Result<Glossary, Error> {
+    let glossary_id = found.id;
+    let mut glossary = to_glossaries(conn, vec![found], me)?.remove(0);
+    glossary.likes = list_likes(conn, glossary_id)?;
+    glossary.relations = list_relations(conn, glossary_id)?;
+    glossary.senses = list_senses(conn, &glossary.term)?;
+    Ok(translate_glossaries(conn, vec![glossary], locales)?.remove(0))
+}
+
+fn glossary_resp(glossary: Glossary) -> HttpResponse {
+    let mut resp = HttpResponse::Ok();
+    resp.insert_header(glossary_etag(&glossary));
+    resp.insert_header((header::VARY, "Accept-Language"));
+    if let Some(locale) = &glossary.locale {
+        resp.insert_header((header::CONTENT_LANGUAGE, locale.clone()));
+    }
+    resp.json(glossary)
+}
+
+/// Find a glossary by slug. The earlier slug of a renamed glossary is
+/// permanently redirected to its current one.
+#[get("/glossary/by-slug/{slug}")]
+pub async fn get_by_slug(
+    pool: web::Data<DBPool>,
+    slug: web::Path<String>,
+    query: web::Query<LangQuery>,
+    req: HttpRequest,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let me = req
+        .headers()
+        .get(crate::AUTHENTICATED_USER_HEADER)
+        .map(|email| email.to_str().unwrap().to_string());
+
+    let locales = preferred_locales(query.lang.as_deref(), &req)?;
+
+    let found = web::block(move || {
+        match get_glossary_by_slug(&mut conn, &slug).optional()? {
+            Some(found) => Ok(Ok(load_glossary(&mut conn, found, me.as_deref(), &locales)?)),
+            None => match find_slug_redirect(&mut conn, &slug)? {
+                Some(current) => Ok(Err(current)),
+                None => Err(Error::NotFound),
+            },
+        }
+    })
+    .await??;
+
+    match found {
+        Ok(glossary) => Ok(glossary_resp(glossary)),
+        Err(current) => {
+            let (base, _) = req.path().rsplit_once('/').unwrap_or_default();
+            let mut location = format!("{}/{}", base, current);
+            if !req.query_string().is_empty() {
+                location = format!("{}?{}", location, req.query_string());
+            }
+            Ok(HttpResponse::MovedPermanently()
+                .insert_header((header::LOCATION, location))
+                .finish())
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TermQuery {
+    pub term: String,
+    pub lang: Option<String>,
+}
+
+/// Find a glossary by its term or one of its aliases, whatever their case.
+/// An earlier term of a renamed or merged glossary redirects to its slug.
+#[get("/glossary/by-term")]
+pub async fn get_by_term(
+    pool: web::Data<DBPool>,
+    query: web::Query<TermQuery>,
+    req: HttpRequest,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let me = req
+        .headers()
+        .get(crate::AUTHENTICATED_USER_HEADER)
+        .map(|email| email.to_str().unwrap().to_string());
+
+    let query = query.into_inner();
+    let locales = preferred_locales(query.lang.as_deref(), &req)?;
+
+    let lang = query.lang.clone();
+    let found = web::block(move || {
+        match get_glossary_by_term(&mut conn, &query.term).optional()? {
+            Some(found) => Ok(Ok(load_glossary(&mut conn, found, me.as_deref(), &locales)?)),
+            None => match find_term_redirect(&mut conn, &query.term)? {
+                Some(current) => Ok(Err(current)),
+                None => Err(Error::NotFound),
+            },
+        }
+    })
+    .await??;
 
-    let glossary = web::block(move || get_glossary(&mut conn, glossary_id)).await??;
-    Ok(web::Json(glossary.to_glossary_with_who_from_db(&mut conn2)))
+    match found {
+        Ok(glossary) => Ok(glossary_resp(glossary)),
+        Err(current) => {
+            let (base, _) = req.path().rsplit_once('/').unwrap_or_default();
+            let mut location = format!("{}/by-slug/{}", base, current);
+            if lang.is_some() {
+                let query = serde_urlencoded::to_string(LangQuery { lang }).unwrap();
+                location = format!("{}?{}", location, query);
+            }
+            Ok(HttpResponse::MovedPermanently()
+                .insert_header((header::LOCATION, location))
+                .finish())
+        }
+    }
 }
 
-/// Update a glossary by id
+/// Update a glossary by id.
+/// Send the last seen `revision` in the body or its ETag in `If-Match` to avoid
+/// overwriting someone else's changes.
 #[put("/glossary/{id}")]
 pub async fn update(
     pool: web::Data<DBPool>,
@@ -353,14 +1701,176 @@ pub async fn update(
 
     let glossary_id = Uuid::from_str(&id)
         .map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+    let expected_revision = match value.revision {
+        Some(revision) => Some(revision),
+        None => parse_if_match(&req)?,
+    };
 
-    let glossary = web::block(move || {
+    let (glossary, tags) = web::block(move || {
+        let mut conn = pool.get().expect("could not get db connection from pool");
+        let glossary = value.to_glossary().unwrap();
+        let edit = Edit {
+            who,
+            status_change: value.status_change(),
+            summary: value.summary,
+            ..Default::default()
+        };
+        let updated = update_glossary(&mut conn, glossary_id, glossary, expected_revision, edit)?;
+        let tags = list_glossary_tag_names(&mut conn, glossary_id)?;
+        Ok::<_, ApiError>((updated, tags))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok()
+        .insert_header(revision_etag(glossary.revision))
+        .json(glossary.to_glossary_with_who(who2).add_tags(tags)))
+}
+
+/// The media type of a JSON Merge Patch
+pub const MERGE_PATCH_CONTENT_TYPE: &str = "application/merge-patch+json";
+
+/// Update only the fields given in a JSON Merge Patch, sent as
+/// `application/merge-patch+json`. A `null` member clears the field.
+/// The patched glossary is validated like a `PUT`, and a patch which changes
+/// nothing does not save a new revision.
+#[patch("/glossary/{id}")]
+pub async fn patch(
+    pool: web::Data<DBPool>,
+    id: web::Path<String>,
+    web::Json(value): web::Json<serde_json::Value>,
+    req: HttpRequest,
+) -> actix_web::Result<impl Responder, ApiError> {
+    if req.content_type() != MERGE_PATCH_CONTENT_TYPE {
+        return Err(ApiError::unsupported_media_type(&format!(
+            "Expected {}",
+            MERGE_PATCH_CONTENT_TYPE
+        )));
+    }
+
+    let who = req
+        .headers()
+        .get(crate::AUTHENTICATED_USER_HEADER)
+        .map(|email| email.to_str().unwrap().to_string());
+    let who2 = who.clone();
+
+    let glossary_id = Uuid::from_str(&id)
+        .map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+    let expected_revision = parse_if_match(&req)?;
+
+    let (glossary, tags) = web::block(move || {
+        let mut conn = pool.get().expect("could not get db connection from pool");
+        let patched = patch_glossary(&mut conn, glossary_id, value, expected_revision, who)?;
+        let tags = list_glossary_tag_names(&mut conn, glossary_id)?;
+        Ok::<_, ApiError>((patched, tags))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok()
+        .insert_header(revision_etag(glossary.revision))
+        .json(glossary.to_glossary_with_who(who2).add_tags(tags)))
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate)]
+pub struct RevertRequest {
+    /// The history revision to copy back into the glossary
+    #[validate(range(min = 0))]
+    pub revision: i32,
+    /// Why the glossary is reverted, kept with the revision in the history
+    #[validate(length(max = 500))]
+    #[serde(
+        default,
+        deserialize_with = "cleanup_string",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub summary: Option<String>,
+}
+
+/// Revert a glossary to an earlier revision.
+/// The reverted term and definition are saved as a new revision.
+#[post("/glossary/{id}/revert")]
+pub async fn revert(
+    pool: web::Data<DBPool>,
+    id: web::Path<String>,
+    Json(value): Json<RevertRequest>,
+    req: HttpRequest,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let who = req
+        .headers()
+        .get(crate::AUTHENTICATED_USER_HEADER)
+        .map(|email| email.to_str().unwrap().to_string());
+    let who2 = who.clone();
+
+    let glossary_id = Uuid::from_str(&id)
+        .map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+    let expected_revision = parse_if_match(&req)?;
+
+    let (glossary, tags) = web::block(move || {
+        let mut conn = pool.get().expect("could not get db connection from pool");
+        let target = get_glossary_revision(&mut conn, glossary_id, value.revision)?;
+        // The replacement of a reverted status may be gone since
+        let replaced_by = target
+            .replaced_by
+            .filter(|r| get_glossary(&mut conn, *r).is_ok());
+        let status_change = StatusChange {
+            status: GlossaryStatus::from_str(&target.status)?,
+            reason: target.status_reason,
+            replaced_by,
+        };
+        let glossary = Glossary {
+            domain: Some(target.domain),
+            abbreviation: Some(target.abbreviation.unwrap_or_default()),
+            expansion: Some(target.expansion.unwrap_or_default()),
+            // The revision may predate the normalization of terms
+            ..Glossary::new(normalize_term(&target.term), target.definition)
+        };
+        let edit = Edit {
+            who,
+            reverted_from: Some(value.revision),
+            status_change: Some(status_change),
+            summary: value.summary,
+        };
+        let updated = update_glossary(&mut conn, glossary_id, glossary, expected_revision, edit)?;
+        let tags = list_glossary_tag_names(&mut conn, glossary_id)?;
+        Ok::<_, ApiError>((updated, tags))
+    })
+    .await??;
+
+    Ok(HttpResponse::Ok()
+        .insert_header(revision_etag(glossary.revision))
+        .json(glossary.to_glossary_with_who(who2).add_tags(tags)))
+}
+
+/// Merge a duplicate glossary into another one.
+/// The duplicate goes to the trash, its term and slug lead to the target from then on.
+#[post("/glossary/{id}/merge-into/{target}")]
+pub async fn merge_into(
+    pool: web::Data<DBPool>,
+    path: web::Path<(String, String)>,
+    req: HttpRequest,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let who = req
+        .headers()
+        .get(crate::AUTHENTICATED_USER_HEADER)
+        .map(|email| email.to_str().unwrap().to_string());
+    let who2 = who.clone();
+
+    let (id, target) = path.into_inner();
+    let glossary_id = Uuid::from_str(&id)
+        .map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+    let target_id = Uuid::from_str(&target)
+        .map_err(|_| ApiError::invalid_input("Invalid target glossary ID format"))?;
+
+    let (glossary, tags) = web::block(move || {
         let mut conn = pool.get().expect("could not get db connection from pool");
-        update_glossary(&mut conn, glossary_id, value.to_glossary().unwrap(), who)
+        let merged = merge_glossary(&mut conn, glossary_id, target_id, who)?;
+        let tags = list_glossary_tag_names(&mut conn, target_id)?;
+        Ok::<_, ApiError>((merged, tags))
     })
     .await??;
 
-    Ok(web::Json(glossary.to_glossary_with_who(who2)))
+    Ok(HttpResponse::Ok()
+        .insert_header(revision_etag(glossary.revision))
+        .json(glossary.to_glossary_with_who(who2).add_tags(tags)))
 }
 
 /// Delete a glossary by id
@@ -368,47 +1878,105 @@ pub async fn update(
 pub async fn delete(
     pool: web::Data<DBPool>,
     id: web::Path<String>,
+    req: HttpRequest,
 ) -> actix_web::Result<impl Responder, ApiError> {
     let mut conn = pool.get().expect("could not get db connection from pool");
     let glossary_id = Uuid::from_str(&id)
         .map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
 
-    web::block(move || delete_glossary(&mut conn, glossary_id)).await??;
+    let who = req
+        .headers()
+        .get(crate::AUTHENTICATED_USER_HEADER)
+        .map(|email| email.to_str().unwrap().to_string());
+
+    web::block(move || delete_glossary(&mut conn, glossary_id, who)).await??;
     Ok(web::Json(Message::new("deleted")))
 }
 
 #[derive(Deserialize)]
 pub struct PopularQuery {
     pub limit: Option<u8>,
+    /// `7d`, `30d` or `all` (default)
+    pub window: Option<String>,
+    /// `top` (default) or `trending`
+    pub mode: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 pub struct SearchQuery {
     pub q: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
 }
 
-/// List popular glossaries
+/// List popular glossaries, `/glossary-popular?limit=10&window=7d&mode=trending`
 #[get("/glossary-popular")]
 pub async fn list_popular(
     pool: web::Data<DBPool>,
     query: web::Query<PopularQuery>,
+    req: HttpRequest,
 ) -> actix_web::Result<impl Responder, ApiError> {
+    let limit = query.limit.unwrap_or(10);
+    let window = PopularWindow::from_str(query.window.as_deref().unwrap_or("all"))?;
+    let mode = PopularMode::from_str(query.mode.as_deref().unwrap_or("top"))?;
+
+    let me = req
+        .headers()
+        .get(crate::AUTHENTICATED_USER_HEADER)
+        .map(|email| email.to_str().unwrap().to_string());
+
     let mut conn = pool.get().expect("could not get db connection from pool");
 
     let glossaries = web::block(move || {
-        let limit = query.limit;
-        list_popular_glossary(&mut conn, limit)
+        list_popular_glossary(
+            &mut conn,
+            limit,
+            window,
+            mode,
+            ReactionKind::Helpful,
+            me.as_deref(),
+        )
     })
     .await??;
 
     Ok(web::Json(glossaries))
 }
 
-/// Search glossaries by term or definition
+#[derive(Deserialize)]
+pub struct SuggestQuery {
+    pub prefix: String,
+    pub limit: Option<u8>,
+}
+
+/// Suggest terms for autocomplete, `/glossary-suggest?prefix=ku&limit=8`
+#[get("/glossary-suggest")]
+pub async fn suggest(
+    pool: web::Data<DBPool>,
+    query: web::Query<SuggestQuery>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let prefix = query.prefix.trim().to_string();
+    if prefix.is_empty() {
+        return Err(ApiError::invalid_input("Prefix cannot be empty"));
+    }
+    let limit = query.limit.unwrap_or(8).clamp(1, 20);
+
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let suggestions = web::block(move || suggest_glossary(&mut conn, &prefix, limit)).await??;
+
+    Ok(web::Json(suggestions))
+}
+
+/// Search glossaries by term or definition, most relevant first,
+/// `/glossary-search?q=...&limit=50&cursor=...`
 #[get("/glossary-search")]
 pub async fn search(
     pool: web::Data<DBPool>,
     query: web::Query<SearchQuery>,
+    req: HttpRequest,
 ) -> actix_web::Result<impl Responder, ApiError> {
     let search_query = query.q.clone();
 
@@ -416,42 +1984,45 @@ pub async fn search(
         return Err(ApiError::invalid_input("Search query cannot be empty"));
     }
 
-    let mut conn = pool.get().expect("could not get db connection from pool");
-    let results = web::block(move || search_glossary(&mut conn, &search_query)).await??;
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, DEFAULT_PAGE_SIZE);
+    let after = query.cursor.as_deref().map(decode_cursor).transpose()?;
+    let path = req.path().to_string();
+    let me = req
+        .headers()
+        .get(crate::AUTHENTICATED_USER_HEADER)
+        .map(|email| email.to_str().unwrap().to_string());
+    let locales = preferred_locales(query.lang.as_deref(), &req)?;
+    let lang = query.lang.clone();
 
-    let mut conn2 = pool.get().expect("could not get db connection from pool");
-    let glossaries: Vec<Glossary> = results
-        .into_iter()
-        .map(|g| g.to_glossary_with_who_from_db(&mut conn2))
-        .collect();
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let resp = web::block(move || {
+        let page = search_glossary(&mut conn, &search_query, after, limit)?;
+        to_page_resp(&mut conn, page, me.as_deref(), &locales, |cursor| {
+            let next = SearchQuery {
+                q: search_query.clone(),
+                limit: Some(limit),
+                cursor: Some(cursor.to_string()),
+                lang: lang.clone(),
+            };
+            format!("{}?{}", path, serde_urlencoded::to_string(next).unwrap())
+        })
+    })
+    .await??;
 
-    Ok(web::Json(Glossaries::from(&glossaries)))
+    Ok(HttpResponse::Ok()
+        .insert_header((header::VARY, "Accept-Language"))
+        .json(resp))
 }
 
 // Tests
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::TestContext;
+    use crate::test_utils::{service_should_ok_and_return_json, TestContext};
     use crate::v1::like::create_like;
+    use crate::v1::relation::RelationKind;
     use actix_web::{http::StatusCode, test, App};
 
-    macro_rules! service_should_ok_and_return_json {
-        ($app:expr, $req:expr) => {{
-            let req = test::TestRequest::from($req).to_request();
-            let resp = test::call_service(&$app, req).await;
-            println!("{:?}", resp);
-
-            assert!(resp.status().is_success());
-            assert_eq!(
-                resp.headers().get("content-type").unwrap(),
-                "application/json"
-            );
-
-            resp
-        }};
-    }
-
     // Insert a glossary into database.
     // Than, using API to get list of glossaries
     #[actix_rt::test]
@@ -467,6 +2038,7 @@ mod tests {
             definition: "test_definition_1".to_string(),
             created_at: Utc::now().naive_utc(),
             updated_at: Utc::now().naive_utc(),
+            ..Default::default()
         };
         let item_2 = GlossaryDB {
             id: Uuid::new_v4(),
@@ -475,6 +2047,7 @@ mod tests {
             definition: "test_definition_2".to_string(),
             created_at: Utc::now().naive_utc(),
             updated_at: Utc::now().naive_utc(),
+            ..Default::default()
         };
 
         // Insert two glossaries
@@ -490,7 +2063,7 @@ mod tests {
         let app = test::init_service(App::new().app_data(pool).service(list)).await;
 
         // Response should be OK and application/json
-        let req = test::TestRequest::get().uri("/glossary");
+        let req = test::TestRequest::get().uri("/glossary?view=grouped");
         let resp = service_should_ok_and_return_json!(app, req);
 
         // The response should be:
@@ -506,6 +2079,230 @@ mod tests {
         assert_eq!(values.len(), 2);
     }
 
+    // Listing glossaries should run the same number of queries
+    // however many glossaries, likes, editors and tags there are
+    #[actix_rt::test]
+    async fn test_list_glossary_constant_queries() {
+        use diesel::connection::InstrumentationEvent;
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let ctx = TestContext::new("test_list_glossary_constant_queries");
+        let pool = ctx.get_pool();
+        let mut conn = pool.get().expect("could not get db connection from pool");
+
+        let queries = Arc::new(AtomicUsize::new(0));
+        let counter = queries.clone();
+        conn.set_instrumentation(move |event: InstrumentationEvent<'_>| {
+            if let InstrumentationEvent::StartQuery { .. } = event {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        let count_queries = |conn: &mut PgConnection| {
+            queries.store(0, Ordering::SeqCst);
+            let page = list_glossary_page(conn, None, DEFAULT_PAGE_SIZE).unwrap();
+            let glossaries = to_glossaries(conn, page.results, None).unwrap();
+            (glossaries, queries.load(Ordering::SeqCst))
+        };
+
+        let insert = |conn: &mut PgConnection, n: usize| {
+            for i in 0..n {
+                let json = Json(GlossaryRequest {
+                    term: Some(format!("term_{}_{}", n, i)),
+                    definition: Some("test_definition".to_string()),
+                    ..Default::default()
+                });
+                let created =
+                    create_glossary(conn, json, Some("editor@example.com".to_string())).unwrap();
+                let _ = create_like(conn, created.id, "reader@example.com");
+                let _ = crate::v1::tag::add_glossary_tag(conn, created.id, "infra");
+            }
+        };
+
+        insert(&mut conn, 1);
+        let (glossaries, few) = count_queries(&mut conn);
+        assert_eq!(glossaries.len(), 1);
+
+        insert(&mut conn, 20);
+        let (glossaries, many) = count_queries(&mut conn);
+        assert_eq!(glossaries.len(), 21);
+        assert_eq!(few, many);
+
+        // Likes count, last editor and tags are still loaded
+        assert!(glossaries.iter().all(|g| g.likes_count == 1));
+        assert!(glossaries
+            .iter()
+            .all(|g| g.who.as_deref() == Some("editor@example.com")));
+        assert!(glossaries.iter().all(|g| g.tags == vec!["infra".to_string()]));
+    }
+
+    // Insert 3 glossaries, list them 2 by 2 following the next links
+    #[actix_rt::test]
+    async fn test_list_glossary_pages() {
+        let ctx = TestContext::new("test_list_glossary_pages");
+        let pool = web::Data::new(ctx.get_pool());
+        let conn = &mut pool.get().expect("could not get db connection from pool");
+
+        for term in ["b_term", "a_term", "c_term"] {
+            diesel::insert_into(glossary::table)
+                .values(GlossaryDB {
+                    id: Uuid::new_v4(),
+                    term: term.to_string(),
+                    definition: "test_definition".to_string(),
+                    ..Default::default()
+                })
+                .execute(conn)
+                .expect("could not insert glossary");
+        }
+
+        let app = test::init_service(App::new().app_data(pool).service(list).service(search)).await;
+
+        let req = test::TestRequest::get().uri("/glossary?limit=2");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let page: Glossaries = test::read_body_json(resp).await;
+        assert_eq!(page.count, 2);
+        assert_eq!(page.total, Some(3));
+        assert_eq!(page.results[0].term, "a_term");
+        assert_eq!(page.results[1].term, "b_term");
+
+        let req = test::TestRequest::get().uri(page.next.as_ref().unwrap());
+        let resp = service_should_ok_and_return_json!(app, req);
+        let page: Glossaries = test::read_body_json(resp).await;
+        assert_eq!(page.count, 1);
+        assert_eq!(page.total, Some(3));
+        assert_eq!(page.results[0].term, "c_term");
+        assert!(page.next_cursor.is_none());
+
+        // Search has the same pagination
+        let req = test::TestRequest::get().uri("/glossary-search?q=term&limit=1");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let page: Glossaries = test::read_body_json(resp).await;
+        assert_eq!(page.count, 1);
+        assert_eq!(page.total, Some(3));
+
+        let req = test::TestRequest::get().uri(page.next.as_ref().unwrap());
+        let resp = service_should_ok_and_return_json!(app, req);
+        let page: Glossaries = test::read_body_json(resp).await;
+        assert_eq!(page.results[0].term, "b_term");
+
+        // Invalid cursor
+        let req = test::TestRequest::get()
+            .uri("/glossary?cursor=zz")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    }
+
+    // Search should rank term matches above definition matches,
+    // and support phrases, exclusions, stemming and accents
+    #[actix_rt::test]
+    async fn test_search_glossary_ranking() {
+        let ctx = TestContext::new("test_search_glossary_ranking");
+        let pool = web::Data::new(ctx.get_pool());
+        let conn = &mut pool.get().expect("could not get db connection from pool");
+
+        for (term, definition) in [
+            ("Deployment", "Rolling out a new version of a service"),
+            ("Kubernetes", "Orchestrates the deployments of containers"),
+            ("Café", "Where engineers drink coffee"),
+        ] {
+            diesel::insert_into(glossary::table)
+                .values(GlossaryDB {
+                    id: Uuid::new_v4(),
+                    term: term.to_string(),
+                    definition: definition.to_string(),
+                    ..Default::default()
+                })
+                .execute(conn)
+                .expect("could not insert glossary");
+        }
+
+        let app = test::init_service(App::new().app_data(pool).service(search)).await;
+
+        let search_uri = |q: &str| format!("/glossary-search?q={}", q);
+
+        // "deployments" matches both by stemming, the term match comes first
+        let req = test::TestRequest::get().uri(&search_uri("deployments"));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let page: Glossaries = test::read_body_json(resp).await;
+        assert_eq!(page.total, Some(2));
+        assert_eq!(page.results[0].term, "Deployment");
+        assert_eq!(page.results[1].term, "Kubernetes");
+
+        // Exclusion
+        let req = test::TestRequest::get().uri(&search_uri("deployment%20-containers"));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let page: Glossaries = test::read_body_json(resp).await;
+        assert_eq!(page.total, Some(1));
+        assert_eq!(page.results[0].term, "Deployment");
+
+        // Quoted phrase
+        let req = test::TestRequest::get().uri(&search_uri("%22new%20version%22"));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let page: Glossaries = test::read_body_json(resp).await;
+        assert_eq!(page.total, Some(1));
+
+        // Accent-insensitive
+        let req = test::TestRequest::get().uri(&search_uri("cafe"));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let page: Glossaries = test::read_body_json(resp).await;
+        assert_eq!(page.total, Some(1));
+        assert_eq!(page.results[0].term, "Café");
+    }
+
+    // Suggest terms by case-insensitive prefix, most liked first
+    #[actix_rt::test]
+    async fn test_suggest_glossary() {
+        let ctx = TestContext::new("test_suggest_glossary");
+        let pool = web::Data::new(ctx.get_pool());
+        let mut conn = pool.get().expect("could not get db connection from pool");
+
+        let mut ids = vec![];
+        for term in ["kubectl", "Kubernetes", "Kafka", "100%_uptime"] {
+            let glossary_id = Uuid::new_v4();
+            diesel::insert_into(glossary::table)
+                .values(GlossaryDB {
+                    id: glossary_id,
+                    term: term.to_string(),
+                    definition: "test_definition".to_string(),
+                    ..Default::default()
+                })
+                .execute(&mut conn)
+                .expect("could not insert glossary");
+            ids.push(glossary_id);
+        }
+        let _ = create_like(&mut conn, ids[1], "reader@example.com");
+
+        let app = test::init_service(App::new().app_data(pool).service(suggest)).await;
+
+        let req = test::TestRequest::get().uri("/glossary-suggest?prefix=KU");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let suggestions: Vec<Suggestion> = test::read_body_json(resp).await;
+        assert_eq!(suggestions.len(), 2);
+        assert_eq!(suggestions[0].term, "Kubernetes");
+        assert_eq!(suggestions[0].id, ids[1]);
+        assert_eq!(suggestions[1].term, "kubectl");
+
+        let req = test::TestRequest::get().uri("/glossary-suggest?prefix=k&limit=1");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let suggestions: Vec<Suggestion> = test::read_body_json(resp).await;
+        assert_eq!(suggestions.len(), 1);
+
+        // LIKE wildcards are matched literally
+        let req = test::TestRequest::get().uri("/glossary-suggest?prefix=100%25_");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let suggestions: Vec<Suggestion> = test::read_body_json(resp).await;
+        assert_eq!(suggestions.len(), 1);
+
+        let req = test::TestRequest::get().uri("/glossary-suggest?prefix=_");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let suggestions: Vec<Suggestion> = test::read_body_json(resp).await;
+        assert!(suggestions.is_empty());
+    }
+
     // Direct into data to Database.
     // Than, using API to get glossary
     #[actix_rt::test]
@@ -524,6 +2321,7 @@ mod tests {
             definition: "test_definition_1".to_string(),
             created_at: Utc::now().naive_utc(),
             updated_at: Utc::now().naive_utc(),
+            ..Default::default()
         };
 
         // Insert two glossaries
@@ -543,6 +2341,19 @@ mod tests {
         assert_eq!(resp.term, "test_term_1");
         assert_eq!(resp.definition, "test_definition_1");
         assert_eq!(resp.revision, 1);
+        assert!(!resp.liked_by_me);
+
+        // liked_by_me is only set for the user who liked the glossary
+        create_like(conn, glossary_id, "reader@example.com").unwrap();
+        for (who, liked) in [("reader@example.com", true), ("other@example.com", false)] {
+            let req = test::TestRequest::get()
+                .uri(&api_url)
+                .insert_header((crate::AUTHENTICATED_USER_HEADER, who));
+            let resp = service_should_ok_and_return_json!(app, req);
+            let resp: Glossary = test::read_body_json(resp).await;
+            assert_eq!(resp.likes_count, 1);
+            assert_eq!(resp.liked_by_me, liked);
+        }
     }
 
     // Get glossary that does not exist - should return 404 NOT FOUND
@@ -593,6 +2404,7 @@ mod tests {
         let glossary_req = GlossaryRequest {
             term: Some("test_term_1".to_string()),
             definition: Some("test_definition_1".to_string()),
+            ..Default::default()
         };
 
         // Response should be OK and application/json
@@ -618,7 +2430,7 @@ mod tests {
 
         let req = test::TestRequest::post()
             .uri("/glossary")
-            .set_json(&glossary_req)
+            .set_json(glossary_req)
             .to_request();
         let resp = test::call_service(&app, req).await;
 
@@ -638,6 +2450,7 @@ mod tests {
             .set_json(&GlossaryRequest {
                 term: Some("test_term_1".to_string()),
                 definition: Some("test_definition_1".to_string()),
+                ..Default::default()
             });
 
         // Response should be OK and application/json
@@ -655,37 +2468,629 @@ mod tests {
         assert_eq!(response_of_create, response_of_get);
     }
 
-    // Using API to create glossary. Than, using API to update glossary.
+    // A term can have one sense per domain, GET returns every sense of the term
     #[actix_rt::test]
-    async fn test_create_glossary_then_update() {
-        let ctx = TestContext::new("test_create_glossary_then_update");
+    async fn test_create_glossary_senses() {
+        let ctx = TestContext::new("test_create_glossary_senses");
         let pool = web::Data::new(ctx.get_pool());
 
-        let services = App::new().app_data(pool).service(create).service(update);
-        let app = test::init_service(services).await;
+        let app = test::init_service(App::new().app_data(pool).service(create).service(get)).await;
 
-        // Response should be OK and application/json
+        // The senses of a term are found whatever the case of the term
+        let mut ids = vec![];
+        let senses = [
+            ("pipeline", Some("CI"), "Stages run on each push"),
+            ("Pipeline", None, "A chain"),
+        ];
+        for (term, domain, definition) in senses {
+            let req = test::TestRequest::post()
+                .uri("/glossary")
+                .set_json(&GlossaryRequest {
+                    term: Some(term.to_string()),
+                    domain: domain.map(str::to_string),
+                    definition: Some(definition.to_string()),
+                    ..Default::default()
+                });
+            let resp = service_should_ok_and_return_json!(app, req);
+            let created: Glossary = test::read_body_json(resp).await;
+            assert_eq!(created.domain.as_deref(), domain);
+            ids.push(created.id);
+        }
+
+        // The same term in the same domain is a 409 CONFLICT
         let req = test::TestRequest::post()
             .uri("/glossary")
             .set_json(&GlossaryRequest {
-                term: Some("test_term_1".to_string()),
-                definition: Some("test_definition_1".to_string()),
-            });
+                term: Some("pipeline".to_string()),
+                domain: Some("CI".to_string()),
+                definition: Some("Another definition".to_string()),
+                ..Default::default()
+            })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        // Both senses are returned, the general sense first
+        let req = test::TestRequest::get().uri(&format!("/glossary/{}", ids[0]));
         let resp = service_should_ok_and_return_json!(app, req);
+        let found: Glossary = test::read_body_json(resp).await;
+        assert_eq!(found.domain.as_deref(), Some("CI"));
+        assert_eq!(found.definition, "Stages run on each push");
+        assert_eq!(found.senses.len(), 2);
+        assert_eq!(found.senses[0].id.to_string(), ids[1]);
+        assert_eq!(found.senses[0].domain, None);
+        assert_eq!(found.senses[0].definition, "A chain");
+        assert_eq!(found.senses[1].domain.as_deref(), Some("CI"));
+    }
 
-        let response_of_create: Glossary = test::read_body_json(resp).await;
+    // Senses which rank the same are all paged through by search
+    #[actix_rt::test]
+    async fn test_search_glossary_senses_pages() {
+        let ctx = TestContext::new("test_search_glossary_senses_pages");
+        let pool = web::Data::new(ctx.get_pool());
+        let conn = &mut pool.get().expect("could not get db connection from pool");
 
-        // Verify that insert correctly
-        assert_eq!(response_of_create.term, "test_term_1");
-        assert_eq!(response_of_create.definition, "test_definition_1");
-        assert_eq!(response_of_create.revision, 0);
+        for sense in ["data", "ci", "build"] {
+            diesel::insert_into(glossary::table)
+                .values(GlossaryDB {
+                    id: Uuid::new_v4(),
+                    term: "Pipeline".to_string(),
+                    domain: sense.to_string(),
+                    definition: "Stages which run one after another".to_string(),
+                    ..Default::default()
+                })
+                .execute(conn)
+                .expect("could not insert glossary");
+        }
+
+        let app = test::init_service(App::new().app_data(pool).service(search)).await;
+
+        let mut domains = vec![];
+        let mut uri = "/glossary-search?q=pipeline&limit=1".to_string();
+        loop {
+            let req = test::TestRequest::get().uri(&uri);
+            let resp = service_should_ok_and_return_json!(app, req);
+            let page: Glossaries = test::read_body_json(resp).await;
+            domains.extend(page.results.into_iter().filter_map(|g| g.domain));
+
+            match page.next_cursor {
+                Some(cursor) => {
+                    uri = format!("/glossary-search?q=pipeline&limit=1&cursor={}", cursor)
+                }
+                None => break,
+            }
+        }
+
+        assert_eq!(domains, vec!["build", "ci", "data"]);
+    }
+
+    // A deprecated glossary needs a reason and can point to its replacement.
+    // It still resolves, ranks lower in search and is left out of popular.
+    #[actix_rt::test]
+    async fn test_deprecate_glossary() {
+        let ctx = TestContext::new("test_deprecate_glossary");
+        let pool = web::Data::new(ctx.get_pool());
+        let mut conn = pool.get().expect("could not get connection from pool");
+
+        let services = App::new()
+            .app_data(pool)
+            .service(create)
+            .service(update)
+            .service(get)
+            .service(search)
+            .service(list_popular);
+        let app = test::init_service(services).await;
+
+        let mut ids = vec![];
+        for (term, definition) in [
+            ("Portal", "Where customers sign in"),
+            ("Legacy Portal", "The portal of the portal, before the new portal"),
+        ] {
+            let req = test::TestRequest::post()
+                .uri("/glossary")
+                .set_json(&GlossaryRequest {
+                    term: Some(term.to_string()),
+                    definition: Some(definition.to_string()),
+                    ..Default::default()
+                });
+            let resp = service_should_ok_and_return_json!(app, req);
+            let created: Glossary = test::read_body_json(resp).await;
+            assert_eq!(created.status, GlossaryStatus::Active);
+            let glossary_id = Uuid::from_str(&created.id).unwrap();
+            create_like(&mut conn, glossary_id, "reader@example.com").unwrap();
+            ids.push(glossary_id);
+        }
+        let (portal, legacy) = (ids[0], ids[1]);
+
+        // Without a reason, or replaced by itself
+        for (reason, replaced_by) in [(None, Some(portal)), (Some("Renamed"), Some(legacy))] {
+            let req = test::TestRequest::put()
+                .uri(&format!("/glossary/{}", legacy))
+                .set_json(&GlossaryRequest {
+                    term: Some("Legacy Portal".to_string()),
+                    definition: Some("The portal of the portal, before the new portal".to_string()),
+                    status: Some(GlossaryStatus::Deprecated),
+                    status_reason: reason.map(str::to_string),
+                    replaced_by,
+                    ..Default::default()
+                })
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        }
+
+        // A replacement without a status
+        let req = test::TestRequest::put()
+            .uri(&format!("/glossary/{}", legacy))
+            .set_json(&GlossaryRequest {
+                term: Some("Legacy Portal".to_string()),
+                definition: Some("The portal of the portal, before the new portal".to_string()),
+                replaced_by: Some(portal),
+                ..Default::default()
+            })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::put()
+            .uri(&format!("/glossary/{}", legacy))
+            .set_json(&GlossaryRequest {
+                term: Some("Legacy Portal".to_string()),
+                definition: Some("The portal of the portal, before the new portal".to_string()),
+                status: Some(GlossaryStatus::Deprecated),
+                status_reason: Some("Renamed to Portal in 2026".to_string()),
+                replaced_by: Some(portal),
+                ..Default::default()
+            });
+        let _ = service_should_ok_and_return_json!(app, req);
+
+        // An edit without a status keeps it
+        let req = test::TestRequest::put()
+            .uri(&format!("/glossary/{}", legacy))
+            .set_json(&GlossaryRequest {
+                term: Some("Legacy Portal".to_string()),
+                definition: Some("The portal of the portal, before the new portal!".to_string()),
+                ..Default::default()
+            });
+        let _ = service_should_ok_and_return_json!(app, req);
+
+        let req = test::TestRequest::get().uri(&format!("/glossary/{}", legacy));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let found: Glossary = test::read_body_json(resp).await;
+        assert_eq!(found.status, GlossaryStatus::Deprecated);
+        assert_eq!(found.status_reason.as_deref(), Some("Renamed to Portal in 2026"));
+        assert_eq!(found.replaced_by, Some(portal.to_string()));
+        // The replacement is a replaced-by relation too
+        assert_eq!(
+            found.relations,
+            vec![Relation {
+                relation: RelationKind::ReplacedBy,
+                id: portal.to_string(),
+                term: "Portal".to_string(),
+            }]
+        );
+
+        let req = test::TestRequest::get().uri("/glossary-search?q=portal");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let page: Glossaries = test::read_body_json(resp).await;
+        let terms: Vec<&str> = page.results.iter().map(|g| g.term.as_str()).collect();
+        assert_eq!(terms, vec!["Portal", "Legacy Portal"]);
+
+        let req = test::TestRequest::get().uri("/glossary-popular");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let glossaries: Vec<Glossary> = test::read_body_json(resp).await;
+        let terms: Vec<&str> = glossaries.iter().map(|g| g.term.as_str()).collect();
+        assert_eq!(terms, vec!["Portal"]);
+
+        // Back to active, the replacement and its relation are gone
+        let req = test::TestRequest::put()
+            .uri(&format!("/glossary/{}", legacy))
+            .set_json(&GlossaryRequest {
+                term: Some("Legacy Portal".to_string()),
+                definition: Some("The portal of the portal, before the new portal!".to_string()),
+                status: Some(GlossaryStatus::Active),
+                ..Default::default()
+            });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let found: Glossary = test::read_body_json(resp).await;
+        assert_eq!(found.replaced_by, None);
+        assert!(list_relations(&mut conn, legacy).unwrap().is_empty());
+    }
+
+    // Terms are normalized to NFC with single spaces, and are unique whatever their
+    // case. A conflict names the glossary already using the term.
+    #[actix_rt::test]
+    async fn test_create_glossary_term_conflict() {
+        let ctx = TestContext::new("test_create_glossary_term_conflict");
+        let pool = web::Data::new(ctx.get_pool());
+
+        let services = App::new().app_data(pool).service(create).service(update);
+        let app = test::init_service(services).await;
+
+        let mut ids = vec![];
+        let terms = [("API", "API"), ("Cafe\u{301}  au\tlait ", "Caf\u{e9} au lait")];
+        for (term, normalized) in terms {
+            let req = test::TestRequest::post()
+                .uri("/glossary")
+                .set_json(&GlossaryRequest {
+                    term: Some(term.to_string()),
+                    definition: Some("test_definition".to_string()),
+                    ..Default::default()
+                });
+            let resp = service_should_ok_and_return_json!(app, req);
+            let created: Glossary = test::read_body_json(resp).await;
+            assert_eq!(created.term, normalized);
+            ids.push(created.id);
+        }
+
+        for (term, existing) in [("api ", &ids[0]), ("CAF\u{c9} AU LAIT", &ids[1])] {
+            let req = test::TestRequest::post()
+                .uri("/glossary")
+                .set_json(&GlossaryRequest {
+                    term: Some(term.to_string()),
+                    definition: Some("test_definition".to_string()),
+                    ..Default::default()
+                })
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+            let error: crate::response::ErrorResp = test::read_body_json(resp).await;
+            assert!(error.error.contains(existing.as_str()));
+            let conflict: TermConflict = serde_json::from_value(error.details.unwrap()).unwrap();
+            assert_eq!(&conflict.id.to_string(), existing);
+        }
+
+        // Renaming to a term in use is a conflict too
+        let req = test::TestRequest::put()
+            .uri(&format!("/glossary/{}", ids[1]))
+            .set_json(&GlossaryRequest {
+                term: Some("Api".to_string()),
+                definition: Some("test_definition".to_string()),
+                ..Default::default()
+            })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+    }
+
+    // Slugs are generated from the term and the domain, a suffix tells apart
+    // terms sharing one. A renamed glossary keeps its earlier slug as a redirect.
+    #[actix_rt::test]
+    async fn test_glossary_slugs() {
+        let ctx = TestContext::new("test_glossary_slugs");
+        let pool = web::Data::new(ctx.get_pool());
+
+        let services = App::new()
+            .app_data(pool)
+            .service(create)
+            .service(get_by_slug)
+            .service(get_by_term)
+            .service(update);
+        let app = test::init_service(services).await;
+
+        let mut created = vec![];
+        let requests = [
+            ("Caf\u{e9} au lait", None, "cafe-au-lait"),
+            ("Cafe-au-lait", None, "cafe-au-lait-2"),
+            ("Pipeline", None, "pipeline"),
+            ("Pipeline", Some("CI"), "pipeline-ci"),
+        ];
+        for (term, domain, expected) in requests {
+            let req = test::TestRequest::post()
+                .uri("/glossary")
+                .set_json(&GlossaryRequest {
+                    term: Some(term.to_string()),
+                    definition: Some("test_definition".to_string()),
+                    domain: domain.map(str::to_string),
+                    ..Default::default()
+                });
+            let resp = service_should_ok_and_return_json!(app, req);
+            let glossary: Glossary = test::read_body_json(resp).await;
+            assert_eq!(glossary.slug, expected);
+            created.push(glossary);
+        }
+
+        let req = test::TestRequest::get().uri("/glossary/by-slug/pipeline-ci");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let found: Glossary = test::read_body_json(resp).await;
+        assert_eq!(found.id, created[3].id);
+
+        let req = test::TestRequest::get().uri("/glossary/by-term?term=pipeline");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let found: Glossary = test::read_body_json(resp).await;
+        assert_eq!(found.id, created[2].id);
+
+        let req = test::TestRequest::put()
+            .uri(&format!("/glossary/{}", created[0].id))
+            .set_json(&GlossaryRequest {
+                term: Some("Coffee with milk".to_string()),
+                definition: Some("test_definition".to_string()),
+                ..Default::default()
+            });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let renamed: Glossary = test::read_body_json(resp).await;
+        assert_eq!(renamed.slug, "coffee-with-milk");
+
+        let req = test::TestRequest::get()
+            .uri("/glossary/by-slug/cafe-au-lait?lang=vi")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(
+            resp.headers().get(header::LOCATION).unwrap(),
+            "/glossary/by-slug/coffee-with-milk?lang=vi"
+        );
+
+        for uri in ["/glossary/by-slug/unknown", "/glossary/by-term?term=unknown"] {
+            let req = test::TestRequest::get().uri(uri).to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::NOT_FOUND, "{}", uri);
+        }
+    }
+
+    // A renamed glossary is still found by its earlier term, through a
+    // redirect to its slug, until another glossary takes that term
+    #[actix_rt::test]
+    async fn test_rename_glossary_redirects_term() {
+        let ctx = TestContext::new("test_rename_glossary_redirects_term");
+        let pool = web::Data::new(ctx.get_pool());
+
+        let services = App::new()
+            .app_data(pool)
+            .service(create)
+            .service(get_by_term)
+            .service(update);
+        let app = test::init_service(services).await;
+
+        let req = test::TestRequest::post().uri("/glossary").set_json(&GlossaryRequest {
+            term: Some("Kubernets".to_string()),
+            definition: Some("test_definition".to_string()),
+            ..Default::default()
+        });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let created: Glossary = test::read_body_json(resp).await;
 
-        // Update glossary
+        let req = test::TestRequest::put()
+            .uri(&format!("/glossary/{}", created.id))
+            .set_json(&GlossaryRequest {
+                term: Some("Kubernetes".to_string()),
+                definition: Some("test_definition".to_string()),
+                ..Default::default()
+            });
+        let _ = service_should_ok_and_return_json!(app, req);
+
+        let req = test::TestRequest::get()
+            .uri("/glossary/by-term?term=kubernets")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(
+            resp.headers().get(header::LOCATION).unwrap(),
+            "/glossary/by-slug/kubernetes"
+        );
+
+        // The language asked for is kept
+        let req = test::TestRequest::get()
+            .uri("/glossary/by-term?term=kubernets&lang=vi")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::MOVED_PERMANENTLY);
+        assert_eq!(
+            resp.headers().get(header::LOCATION).unwrap(),
+            "/glossary/by-slug/kubernetes?lang=vi"
+        );
+
+        let req = test::TestRequest::post().uri("/glossary").set_json(&GlossaryRequest {
+            term: Some("Kubernets".to_string()),
+            definition: Some("A common typo".to_string()),
+            ..Default::default()
+        });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let typo: Glossary = test::read_body_json(resp).await;
+
+        let req = test::TestRequest::get().uri("/glossary/by-term?term=kubernets");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let found: Glossary = test::read_body_json(resp).await;
+        assert_eq!(found.id, typo.id);
+    }
+
+    // Merging a duplicate moves its likes, translations, relations and history
+    // over to the target, records the merge, puts the duplicate in the trash and
+    // redirects its term and slug to the target
+    #[actix_rt::test]
+    async fn test_merge_glossary() {
+        use crate::test_utils::insert_glossary;
+        use crate::v1::glossary_history::list_glossary_history_page;
+        use crate::v1::relation::add_relation;
+        use crate::v1::translation::{list_translations, save_translation, TranslationRequest};
+
+        let ctx = TestContext::new("test_merge_glossary");
+        let pool = ctx.get_pool();
+        let conn = &mut pool.get().expect("could not get db connection from pool");
+
+        let services = App::new()
+            .app_data(web::Data::new(pool.clone()))
+            .service(create)
+            .service(get_by_slug)
+            .service(get_by_term)
+            .service(update)
+            .service(revert)
+            .service(crate::v1::glossary_history::diff)
+            .service(merge_into);
+        let app = test::init_service(services).await;
+
+        let mut created = vec![];
+        for term in ["SLA", "SLAs"] {
+            let req = test::TestRequest::post().uri("/glossary").set_json(&GlossaryRequest {
+                term: Some(term.to_string()),
+                definition: Some("Service level agreement".to_string()),
+                ..Default::default()
+            });
+            let resp = service_should_ok_and_return_json!(app, req);
+            let glossary: Glossary = test::read_body_json(resp).await;
+            created.push(Uuid::from_str(&glossary.id).unwrap());
+        }
+        let (target, duplicate) = (created[0], created[1]);
+
+        let req = test::TestRequest::put()
+            .uri(&format!("/glossary/{}", duplicate))
+            .set_json(&GlossaryRequest {
+                term: Some("SLAs".to_string()),
+                definition: Some("Service level agreements".to_string()),
+                ..Default::default()
+            });
+        let _ = service_should_ok_and_return_json!(app, req);
+
+        create_like(conn, target, "alice@example.com").unwrap();
+        create_like(conn, duplicate, "alice@example.com").unwrap();
+        create_like(conn, duplicate, "bob@example.com").unwrap();
+
+        let uptime = insert_glossary(conn, "Uptime", "test_definition");
+        add_relation(conn, duplicate, RelationKind::Related, uptime).unwrap();
+        add_relation(conn, duplicate, RelationKind::Related, target).unwrap();
+
+        let translation = |term: &str| TranslationRequest {
+            term: Some(term.to_string()),
+            definition: Some("Thỏa thuận mức dịch vụ".to_string()),
+        };
+        save_translation(conn, duplicate, "vi", translation("SLA"), None).unwrap();
+
+        // Both translated to the same locale, the merge is refused
+        let conflicting = insert_glossary(conn, "Service level", "test_definition");
+        save_translation(conn, conflicting, "vi", translation("Mức dịch vụ"), None).unwrap();
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/merge-into/{}", conflicting, duplicate))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+        assert!(get_glossary(conn, conflicting).is_ok());
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/merge-into/{}", duplicate, target))
+            .insert_header((crate::AUTHENTICATED_USER_HEADER, "carol@example.com"));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let merged: Glossary = test::read_body_json(resp).await;
+        assert_eq!(merged.id, target.to_string());
+        // The merge is the only new revision of the target
+        assert_eq!(merged.revision, 1);
+
+        // The duplicate is in the trash, with the like which did not move
+        assert!(get_glossary(conn, duplicate).is_err());
+        let trashed = glossary::table
+            .find(duplicate)
+            .first::<GlossaryDB>(conn)
+            .unwrap();
+        assert!(trashed.deleted_at.is_some());
+        assert_eq!(list_likes(conn, target).unwrap().len(), 2);
+        assert_eq!(list_likes(conn, duplicate).unwrap().len(), 1);
+
+        let translations = list_translations(conn, target).unwrap();
+        assert_eq!(translations.len(), 1);
+        assert!(translations[0].outdated);
+        let relations = list_relations(conn, target).unwrap();
+        assert_eq!(relations.len(), 1);
+        assert_eq!(relations[0].id, uptime.to_string());
+
+        // The revisions of the duplicate are copied along, out of those of the target
+        let history = list_glossary_history_page(conn, target, 10, 0).unwrap();
+        let actions: Vec<(&str, i32, Option<Uuid>)> = history
+            .iter()
+            .map(|h| (h.action.as_str(), h.revision, h.merged_from))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("merge", 1, Some(duplicate)),
+                ("update", 1, Some(duplicate)),
+                ("create", 0, Some(duplicate)),
+                ("create", 0, None),
+            ]
+        );
+        assert_eq!(history[0].who, Some("carol@example.com".to_string()));
+        assert!(get_glossary_revision(conn, target, 2).is_err());
+
+        // Diff and revert only reach the revisions of the target
+        let req = test::TestRequest::get()
+            .uri(&format!("/glossary/{}/diff?from=0&to=1", target))
+            .to_request();
+        let diff: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(diff["from"]["term"], "SLA");
+        assert_eq!(diff["term_changed"], false);
+        assert!(!diff["unified"].as_str().unwrap().contains("agreements"));
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/revert", target))
+            .set_json(RevertRequest {
+                revision: 0,
+                summary: None,
+            });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let reverted: Glossary = test::read_body_json(resp).await;
+        assert_eq!(reverted.term, "SLA");
+        assert_eq!(reverted.definition, "Service level agreement");
+
+        // The duplicate keeps its own history in the trash
+        let history = list_glossary_history_page(conn, duplicate, 10, 0).unwrap();
+        let actions: Vec<&str> = history.iter().map(|h| h.action.as_str()).collect();
+        assert_eq!(actions, vec!["delete", "update", "create"]);
+
+        for uri in ["/glossary/by-term?term=slas", "/glossary/by-slug/slas"] {
+            let req = test::TestRequest::get().uri(uri).to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), StatusCode::MOVED_PERMANENTLY, "{}", uri);
+            assert_eq!(
+                resp.headers().get(header::LOCATION).unwrap(),
+                "/glossary/by-slug/sla"
+            );
+        }
+
+        let cases = [
+            (target, target, StatusCode::BAD_REQUEST),
+            (duplicate, target, StatusCode::NOT_FOUND),
+        ];
+        for (from, into, status) in cases {
+            let req = test::TestRequest::post()
+                .uri(&format!("/glossary/{}/merge-into/{}", from, into))
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), status);
+        }
+    }
+
+    // Using API to create glossary. Than, using API to update glossary.
+    #[actix_rt::test]
+    async fn test_create_glossary_then_update() {
+        let ctx = TestContext::new("test_create_glossary_then_update");
+        let pool = web::Data::new(ctx.get_pool());
+
+        let services = App::new().app_data(pool).service(create).service(update);
+        let app = test::init_service(services).await;
+
+        // Response should be OK and application/json
+        let req = test::TestRequest::post()
+            .uri("/glossary")
+            .set_json(&GlossaryRequest {
+                term: Some("test_term_1".to_string()),
+                definition: Some("test_definition_1".to_string()),
+                ..Default::default()
+            });
+        let resp = service_should_ok_and_return_json!(app, req);
+
+        let response_of_create: Glossary = test::read_body_json(resp).await;
+
+        // Verify that insert correctly
+        assert_eq!(response_of_create.term, "test_term_1");
+        assert_eq!(response_of_create.definition, "test_definition_1");
+        assert_eq!(response_of_create.revision, 0);
+
+        // Update glossary
         let req = test::TestRequest::put()
             .uri(&format!("/glossary/{}", response_of_create.id))
             .set_json(&GlossaryRequest {
                 term: Some("test_term_1_updated".to_string()),
                 definition: Some("test_definition_1_updated".to_string()),
+                ..Default::default()
             });
         let resp = service_should_ok_and_return_json!(app, req);
 
@@ -696,6 +3101,265 @@ mod tests {
         assert_eq!(response_of_update.revision, 1);
     }
 
+    // A merge patch updates the fields it gives, `null` clears a field. The
+    // patched glossary is validated, and a patch changing nothing saves nothing.
+    #[actix_rt::test]
+    async fn test_patch_glossary() {
+        use crate::v1::glossary_history::list_glossary_history;
+
+        let ctx = TestContext::new("test_patch_glossary");
+        let pool = ctx.get_pool();
+        let conn = &mut pool.get().expect("could not get db connection from pool");
+
+        let services = App::new()
+            .app_data(web::Data::new(pool.clone()))
+            .service(create)
+            .service(patch);
+        let app = test::init_service(services).await;
+
+        let req = test::TestRequest::post().uri("/glossary").set_json(&GlossaryRequest {
+            term: Some("API".to_string()),
+            definition: Some("test_definition".to_string()),
+            expansion: Some("Application Programming Interface".to_string()),
+            ..Default::default()
+        });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let created: Glossary = test::read_body_json(resp).await;
+        let uri = format!("/glossary/{}", created.id);
+        let glossary_id = Uuid::from_str(&created.id).unwrap();
+
+        let patches = [
+            (r#"{"definition": "A contract between programs"}"#, 1),
+            (r#"{"expansion": null}"#, 2),
+            (r#"{"definition": "A contract between programs", "domain": ""}"#, 2),
+        ];
+        for (body, revision) in patches {
+            let req = test::TestRequest::patch()
+                .uri(&uri)
+                .insert_header((header::CONTENT_TYPE, MERGE_PATCH_CONTENT_TYPE))
+                .set_payload(body);
+            let resp = service_should_ok_and_return_json!(app, req);
+            let patched: Glossary = test::read_body_json(resp).await;
+            assert_eq!(patched.revision, revision, "{}", body);
+            assert_eq!(patched.term, "API");
+            assert_eq!(patched.definition, "A contract between programs");
+        }
+
+        let found = get_glossary(conn, glossary_id).unwrap();
+        assert_eq!(found.abbreviation, Some("API".to_string()));
+        assert_eq!(found.expansion, None);
+        assert_eq!(list_glossary_history(conn, glossary_id).unwrap().len(), 3);
+
+        let merge_patch = MERGE_PATCH_CONTENT_TYPE;
+        let cases = [
+            (merge_patch, r#"{"term": null}"#, StatusCode::UNPROCESSABLE_ENTITY),
+            (merge_patch, r#"{"term": ""}"#, StatusCode::BAD_REQUEST),
+            (merge_patch, r#"{"definition": 5}"#, StatusCode::UNPROCESSABLE_ENTITY),
+            (merge_patch, r#"{"revision": 0, "definition": "stale"}"#, StatusCode::CONFLICT),
+            ("application/json", r#"{"definition": "json"}"#, StatusCode::UNSUPPORTED_MEDIA_TYPE),
+        ];
+        for (content_type, body, status) in cases {
+            let req = test::TestRequest::patch()
+                .uri(&uri)
+                .insert_header((header::CONTENT_TYPE, content_type))
+                .set_payload(body)
+                .to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), status, "{}", body);
+        }
+    }
+
+    // Two editors update the same revision, the second update should
+    // return 409 CONFLICT with the revision saved by the first one.
+    #[actix_rt::test]
+    async fn test_update_glossary_stale_revision() {
+        let ctx = TestContext::new("test_update_glossary_stale_revision");
+        let pool = web::Data::new(ctx.get_pool());
+
+        let services = App::new().app_data(pool).service(create).service(update);
+        let app = test::init_service(services).await;
+
+        let req = test::TestRequest::post()
+            .uri("/glossary")
+            .set_json(&GlossaryRequest {
+                term: Some("test_term_1".to_string()),
+                definition: Some("test_definition_1".to_string()),
+                ..Default::default()
+            });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let response_of_create: Glossary = test::read_body_json(resp).await;
+
+        // The first editor saves on top of revision 0
+        let req = test::TestRequest::put()
+            .uri(&format!("/glossary/{}", response_of_create.id))
+            .set_json(&GlossaryRequest {
+                term: Some("test_term_1".to_string()),
+                definition: Some("first editor".to_string()),
+                revision: Some(0),
+                ..Default::default()
+            });
+        let resp = service_should_ok_and_return_json!(app, req);
+        assert_eq!(resp.headers().get("etag").unwrap(), "\"1\"");
+
+        // The second editor also saw revision 0
+        let req = test::TestRequest::put()
+            .uri(&format!("/glossary/{}", response_of_create.id))
+            .set_json(&GlossaryRequest {
+                term: Some("test_term_1".to_string()),
+                definition: Some("second editor".to_string()),
+                revision: Some(0),
+                ..Default::default()
+            })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+
+        let error: crate::response::ErrorResp = test::read_body_json(resp).await;
+        let current: RevisionConflict = serde_json::from_value(error.details.unwrap()).unwrap();
+        assert_eq!(current.revision, 1);
+        assert_eq!(current.definition, "first editor");
+    }
+
+    // Update with the ETag of the current revision in If-Match should success,
+    // update with a stale ETag should return 409 CONFLICT.
+    #[actix_rt::test]
+    async fn test_update_glossary_if_match() {
+        let ctx = TestContext::new("test_update_glossary_if_match");
+        let pool = web::Data::new(ctx.get_pool());
+
+        let services = App::new()
+            .app_data(pool)
+            .service(create)
+            .service(get)
+            .service(update);
+        let app = test::init_service(services).await;
+
+        let req = test::TestRequest::post()
+            .uri("/glossary")
+            .set_json(&GlossaryRequest {
+                term: Some("test_term_1".to_string()),
+                definition: Some("test_definition_1".to_string()),
+                ..Default::default()
+            });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let response_of_create: Glossary = test::read_body_json(resp).await;
+
+        let req = test::TestRequest::get().uri(&format!("/glossary/{}", response_of_create.id));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let etag = resp.headers().get("etag").unwrap().clone();
+
+        let req = test::TestRequest::put()
+            .uri(&format!("/glossary/{}", response_of_create.id))
+            .insert_header((header::IF_MATCH, etag.clone()))
+            .set_json(&GlossaryRequest {
+                term: Some("test_term_1".to_string()),
+                definition: Some("test_definition_1_updated".to_string()),
+                ..Default::default()
+            });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let response_of_update: Glossary = test::read_body_json(resp).await;
+        assert_eq!(response_of_update.revision, 1);
+
+        // The same ETag is stale now
+        let req = test::TestRequest::put()
+            .uri(&format!("/glossary/{}", response_of_create.id))
+            .insert_header((header::IF_MATCH, etag))
+            .set_json(&GlossaryRequest {
+                term: Some("test_term_1".to_string()),
+                definition: Some("test_definition_1_stale".to_string()),
+                ..Default::default()
+            })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::CONFLICT);
+    }
+
+    // Create a glossary, update it, then revert it to the first revision.
+    // The revert should be a new revision attributed to the caller.
+    #[actix_rt::test]
+    async fn test_revert_glossary() {
+        use crate::v1::glossary_history::list_glossary_history;
+
+        let ctx = TestContext::new("test_revert_glossary");
+        let pool = web::Data::new(ctx.get_pool());
+        let mut conn = pool.get().expect("could not get connection from pool");
+
+        let services = App::new()
+            .app_data(pool)
+            .service(create)
+            .service(update)
+            .service(revert);
+        let app = test::init_service(services).await;
+
+        let req = test::TestRequest::post()
+            .uri("/glossary")
+            .set_json(&GlossaryRequest {
+                term: Some("test_term_1".to_string()),
+                definition: Some("test_definition_1".to_string()),
+                ..Default::default()
+            });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let response_of_create: Glossary = test::read_body_json(resp).await;
+
+        let req = test::TestRequest::put()
+            .uri(&format!("/glossary/{}", response_of_create.id))
+            .set_json(&GlossaryRequest {
+                term: Some("vandalized".to_string()),
+                definition: Some("vandalized".to_string()),
+                ..Default::default()
+            });
+        let _ = service_should_ok_and_return_json!(app, req);
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/revert", response_of_create.id))
+            .insert_header((crate::AUTHENTICATED_USER_HEADER, "admin@example.com"))
+            .set_json(RevertRequest {
+                revision: 0,
+                summary: Some("Undo the rename".to_string()),
+            });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let response_of_revert: Glossary = test::read_body_json(resp).await;
+        assert_eq!(response_of_revert.term, "test_term_1");
+        assert_eq!(response_of_revert.definition, "test_definition_1");
+        assert_eq!(response_of_revert.revision, 2);
+
+        let glossary_id = Uuid::from_str(&response_of_create.id).unwrap();
+        let histories = list_glossary_history(&mut conn, glossary_id).unwrap();
+        let latest = histories.first().unwrap();
+        assert_eq!(latest.revision, 2);
+        assert_eq!(latest.reverted_from, Some(0));
+        assert_eq!(latest.who, Some("admin@example.com".to_string()));
+        assert_eq!(latest.summary.as_deref(), Some("Undo the rename"));
+
+        // A revision saved before terms were normalized is normalized back
+        diesel::update(crate::schema::glossary_history::table)
+            .filter(crate::schema::glossary_history::glossary_id.eq(glossary_id))
+            .filter(crate::schema::glossary_history::revision.eq(0))
+            .set(crate::schema::glossary_history::term.eq("  test   term_1 "))
+            .execute(&mut conn)
+            .unwrap();
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/revert", response_of_create.id))
+            .set_json(RevertRequest {
+                revision: 0,
+                summary: None,
+            });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let response_of_revert: Glossary = test::read_body_json(resp).await;
+        assert_eq!(response_of_revert.term, "test term_1");
+
+        // Revert to a revision that does not exist
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/revert", response_of_create.id))
+            .set_json(RevertRequest {
+                revision: 9,
+                summary: None,
+            })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+    }
+
     // Using API to create glossary. Than, using API to delete glossary.
     #[actix_rt::test]
     async fn test_create_glossary_then_delete() {
@@ -716,6 +3380,7 @@ mod tests {
             .set_json(&GlossaryRequest {
                 term: Some("test_term_1".to_string()),
                 definition: Some("test_definition_1".to_string()),
+                ..Default::default()
             });
 
         // Response should be OK and application/json
@@ -786,6 +3451,7 @@ mod tests {
             .set_json(&GlossaryRequest {
                 term: Some("test_term_1".to_string()),
                 definition: Some("test_definition_1".to_string()),
+                ..Default::default()
             });
         let resp = service_should_ok_and_return_json!(app, req);
 
@@ -796,12 +3462,94 @@ mod tests {
         assert_eq!(response_of_create.revision, 0);
 
         let glossary_id = Uuid::from_str(&response_of_create.id).unwrap();
-        let _ = create_like(&mut conn, glossary_id, None);
+        let _ = create_like(&mut conn, glossary_id, "reader@example.com");
 
         // Get the list popular
         let req = test::TestRequest::get().uri("/glossary-popular");
         let resp = service_should_ok_and_return_json!(app, req);
         let response_of_list_popular: Vec<Glossary> = test::read_body_json(resp).await;
         assert_eq!(response_of_list_popular.len(), 1);
+        assert_eq!(response_of_list_popular[0].likes_count, 1);
+    }
+
+    // Popular glossaries come in rank order with their likes count,
+    // within a time window or decayed by the age of the likes
+    #[actix_rt::test]
+    async fn test_list_popular_glossaries_ranking() {
+        let ctx = TestContext::new("test_list_popular_glossaries_ranking");
+        let pool = web::Data::new(ctx.get_pool());
+        let mut conn = pool.get().expect("could not get connection from pool");
+
+        // "old" has 3 likes from 60 days ago, "new" has 2 likes from today
+        // and "mid" has 1 like from 10 days ago
+        let now = Utc::now().naive_utc();
+        for (term, count, days_ago) in [("old", 3, 60), ("new", 2, 0), ("mid", 1, 10)] {
+            let glossary_id = Uuid::new_v4();
+            diesel::insert_into(glossary::table)
+                .values(GlossaryDB {
+                    id: glossary_id,
+                    term: term.to_string(),
+                    definition: "test_definition".to_string(),
+                    ..Default::default()
+                })
+                .execute(&mut conn)
+                .expect("could not insert glossary");
+
+            for _ in 0..count {
+                diesel::insert_into(likes::table)
+                    .values((
+                        likes::id.eq(Uuid::new_v4()),
+                        likes::glossary_id.eq(glossary_id),
+                        likes::created_at.eq(now - chrono::Duration::days(days_ago)),
+                    ))
+                    .execute(&mut conn)
+                    .expect("could not insert like");
+            }
+        }
+
+        let app = test::init_service(App::new().app_data(pool).service(list_popular)).await;
+
+        let popular_terms = |glossaries: &[Glossary]| {
+            glossaries
+                .iter()
+                .map(|g| (g.term.clone(), g.likes_count))
+                .collect::<Vec<_>>()
+        };
+
+        let req = test::TestRequest::get().uri("/glossary-popular");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let glossaries: Vec<Glossary> = test::read_body_json(resp).await;
+        assert_eq!(
+            popular_terms(&glossaries),
+            vec![("old".to_string(), 3), ("new".to_string(), 2), ("mid".to_string(), 1)]
+        );
+
+        let req = test::TestRequest::get().uri("/glossary-popular?window=30d");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let glossaries: Vec<Glossary> = test::read_body_json(resp).await;
+        assert_eq!(
+            popular_terms(&glossaries),
+            vec![("new".to_string(), 2), ("mid".to_string(), 1)]
+        );
+
+        let req = test::TestRequest::get().uri("/glossary-popular?window=7d");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let glossaries: Vec<Glossary> = test::read_body_json(resp).await;
+        assert_eq!(popular_terms(&glossaries), vec![("new".to_string(), 2)]);
+
+        // 3 likes from 60 days ago weigh less than 1 like from 10 days ago
+        let req = test::TestRequest::get().uri("/glossary-popular?mode=trending&limit=2");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let glossaries: Vec<Glossary> = test::read_body_json(resp).await;
+        assert_eq!(
+            popular_terms(&glossaries),
+            vec![("new".to_string(), 2), ("mid".to_string(), 1)]
+        );
+
+        let req = test::TestRequest::get()
+            .uri("/glossary-popular?window=1y")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
     }
 }
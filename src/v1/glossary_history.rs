@@ -1,11 +1,89 @@
-use chrono::{NaiveDateTime, Utc};
+use actix_web::{get, web, Responder};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
 use diesel::{
-    result::Error, ExpressionMethods, Insertable, PgConnection, QueryDsl, Queryable, RunQueryDsl,
+    result::Error, BoolExpressionMethods, ExpressionMethods, Insertable, PgConnection, QueryDsl,
+    Queryable, RunQueryDsl,
 };
 use log::info;
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+use std::{collections::HashMap, str::FromStr};
 use uuid::Uuid;
 
-use crate::schema::*;
+use super::glossary::{GlossaryDB, GlossaryStatus};
+use crate::{
+    response::{ApiError, ListResp},
+    schema::*,
+    DBPool,
+};
+
+pub type GlossaryRevisions = ListResp<GlossaryRevision>;
+
+/// What a revision did to the glossary
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryAction {
+    Create,
+    Update,
+    Delete,
+    Revert,
+    Restore,
+    Merge,
+}
+
+impl HistoryAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HistoryAction::Create => "create",
+            HistoryAction::Update => "update",
+            HistoryAction::Delete => "delete",
+            HistoryAction::Revert => "revert",
+            HistoryAction::Restore => "restore",
+            HistoryAction::Merge => "merge",
+        }
+    }
+}
+
+impl FromStr for HistoryAction {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "create" => Ok(HistoryAction::Create),
+            "update" => Ok(HistoryAction::Update),
+            "delete" => Ok(HistoryAction::Delete),
+            "revert" => Ok(HistoryAction::Revert),
+            "restore" => Ok(HistoryAction::Restore),
+            "merge" => Ok(HistoryAction::Merge),
+            _ => Err(ApiError::internal(&format!("Unknown history action: {}", s))),
+        }
+    }
+}
+
+/// One saved revision of a glossary, as returned by the history API
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct GlossaryRevision {
+    pub revision: i32,
+    pub action: HistoryAction,
+    pub term: String,
+    /// The domain of the sense, `None` for the general sense
+    pub domain: Option<String>,
+    pub abbreviation: Option<String>,
+    pub expansion: Option<String>,
+    pub status: GlossaryStatus,
+    pub status_reason: Option<String>,
+    pub replaced_by: Option<Uuid>,
+    pub definition: String,
+    pub who: Option<String>,
+    pub created_at: DateTime<Utc>,
+    /// Set when this revision reverted the glossary to an earlier revision
+    pub reverted_from: Option<i32>,
+    /// The glossary merged into this one, on the merge revision and on the
+    /// revisions moved over from the merged glossary
+    pub merged_from: Option<Uuid>,
+    /// Why the edit was made, as given by its author
+    pub summary: Option<String>,
+}
 
 #[derive(Debug, Queryable, Insertable)]
 #[diesel(table_name = glossary_history)]
@@ -17,30 +95,192 @@ pub struct GlossaryHistoryDB {
     pub who: Option<String>,
     pub created_at: NaiveDateTime,
     pub glossary_id: Uuid,
+    pub reverted_from: Option<i32>,
+    pub action: String,
+    pub domain: String,
+    pub abbreviation: Option<String>,
+    pub expansion: Option<String>,
+    pub status: String,
+    pub status_reason: Option<String>,
+    pub replaced_by: Option<Uuid>,
+    pub merged_from: Option<Uuid>,
+    pub summary: Option<String>,
+}
+
+impl GlossaryHistoryDB {
+    pub fn new(
+        saved: &GlossaryDB,
+        who: Option<String>,
+        action: HistoryAction,
+        reverted_from: Option<i32>,
+        summary: Option<String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            term: saved.term.clone(),
+            definition: saved.definition.clone(),
+            revision: saved.revision,
+            glossary_id: saved.id,
+            who,
+            created_at: Utc::now().naive_utc(),
+            reverted_from,
+            action: action.as_str().to_string(),
+            domain: saved.domain.clone(),
+            abbreviation: saved.abbreviation.clone(),
+            expansion: saved.expansion.clone(),
+            status: saved.status.clone(),
+            status_reason: saved.status_reason.clone(),
+            replaced_by: saved.replaced_by,
+            merged_from: None,
+            summary: summary.filter(|s| !s.is_empty()),
+        }
+    }
+
+    pub fn to_glossary_revision(&self) -> GlossaryRevision {
+        GlossaryRevision {
+            revision: self.revision,
+            action: HistoryAction::from_str(&self.action).unwrap_or(HistoryAction::Update),
+            term: self.term.clone(),
+            domain: Some(self.domain.clone()).filter(|d| !d.is_empty()),
+            abbreviation: self.abbreviation.clone(),
+            expansion: self.expansion.clone(),
+            status: GlossaryStatus::from_str(&self.status).unwrap_or_default(),
+            status_reason: self.status_reason.clone(),
+            replaced_by: self.replaced_by,
+            definition: self.definition.clone(),
+            who: self.who.clone(),
+            created_at: Utc.from_utc_datetime(&self.created_at),
+            reverted_from: self.reverted_from,
+            merged_from: self.merged_from,
+            summary: self.summary.clone(),
+        }
+    }
+}
+
+/// A run of consecutive words with the same change in a definition diff
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct DiffSegment {
+    pub op: DiffOp,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffOp {
+    Equal,
+    Insert,
+    Delete,
+}
+
+impl From<ChangeTag> for DiffOp {
+    fn from(tag: ChangeTag) -> Self {
+        match tag {
+            ChangeTag::Equal => DiffOp::Equal,
+            ChangeTag::Insert => DiffOp::Insert,
+            ChangeTag::Delete => DiffOp::Delete,
+        }
+    }
+}
+
+/// Changes between two revisions of a glossary
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct GlossaryDiff {
+    pub from: GlossaryRevision,
+    pub to: GlossaryRevision,
+    pub term_changed: bool,
+    /// Word-level diff of the definition
+    pub definition: Vec<DiffSegment>,
+    /// Line-based unified diff of the term and definition
+    pub unified: String,
+}
+
+impl GlossaryDiff {
+    pub fn new(from: GlossaryRevision, to: GlossaryRevision) -> Self {
+        let mut definition: Vec<DiffSegment> = vec![];
+        for change in TextDiff::from_words(&from.definition, &to.definition).iter_all_changes() {
+            let op = DiffOp::from(change.tag());
+            match definition.last_mut() {
+                Some(last) if last.op == op => last.text.push_str(change.value()),
+                _ => definition.push(DiffSegment {
+                    op,
+                    text: change.value().to_string(),
+                }),
+            }
+        }
+
+        let old = format!("{}\n\n{}\n", from.term, from.definition);
+        let new = format!("{}\n\n{}\n", to.term, to.definition);
+        let unified = TextDiff::from_lines(&old, &new)
+            .unified_diff()
+            .header(
+                &format!("revision {}", from.revision),
+                &format!("revision {}", to.revision),
+            )
+            .to_string();
+
+        Self {
+            term_changed: from.term != to.term,
+            definition,
+            unified,
+            from,
+            to,
+        }
+    }
 }
 
+/// Record a saved glossary as a new revision in its history.
+/// `reverted_from` is the revision a `HistoryAction::Revert` copied back,
+/// `summary` what the author said about the edit.
 pub fn create_glossary_history(
     conn: &mut PgConnection,
-    term: String,
-    definition: String,
+    saved: &GlossaryDB,
     who: Option<String>,
-    revision: i32,
-    glossary_id: Uuid,
-) {
-    let _glossary_history = GlossaryHistoryDB {
-        id: Uuid::new_v4(),
-        term,
-        definition,
-        revision,
-        glossary_id,
-        who,
-        created_at: Utc::now().naive_utc(),
-    };
+    action: HistoryAction,
+    reverted_from: Option<i32>,
+    summary: Option<String>,
+) -> Result<usize, Error> {
+    let _glossary_history = GlossaryHistoryDB::new(saved, who, action, reverted_from, summary);
 
     info!("Insert a history revison: {:?}", _glossary_history);
-    let _ = diesel::insert_into(glossary_history::table)
+    diesel::insert_into(glossary_history::table)
         .values(_glossary_history)
-        .execute(conn);
+        .execute(conn)
+}
+
+/// Copy the history of a merged glossary over to the glossary it was merged
+/// into, then record the merge as a new revision of the latter. The copies keep
+/// the revision numbers of the merged glossary and point to it with `merged_from`,
+/// they are not revisions of the glossary merged into: a revision lookup, and so
+/// diff and revert, skips them. The merged glossary keeps its own history.
+pub fn merge_glossary_history(
+    conn: &mut PgConnection,
+    merged: &GlossaryDB,
+    from: Uuid,
+    _who: Option<String>,
+) -> Result<usize, Error> {
+    use crate::schema::glossary_history::dsl::*;
+
+    let copies: Vec<GlossaryHistoryDB> = glossary_history
+        .filter(glossary_id.eq(from))
+        .load::<GlossaryHistoryDB>(conn)?
+        .into_iter()
+        .map(|history| GlossaryHistoryDB {
+            id: Uuid::new_v4(),
+            glossary_id: merged.id,
+            merged_from: Some(from),
+            ..history
+        })
+        .collect();
+    diesel::insert_into(glossary_history)
+        .values(&copies)
+        .execute(conn)?;
+
+    diesel::insert_into(glossary_history)
+        .values(GlossaryHistoryDB {
+            merged_from: Some(from),
+            ..GlossaryHistoryDB::new(merged, _who, HistoryAction::Merge, None, None)
+        })
+        .execute(conn)
 }
 
 pub fn list_glossary_history(
@@ -54,3 +294,427 @@ pub fn list_glossary_history(
         .order(created_at.desc())
         .load::<GlossaryHistoryDB>(conn)
 }
+
+/// The author of the latest revision of several glossaries at once
+pub fn list_last_editors(
+    conn: &mut PgConnection,
+    glossary_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Option<String>>, Error> {
+    use crate::schema::glossary_history::dsl::*;
+
+    let editors = glossary_history
+        .filter(glossary_id.eq_any(glossary_ids))
+        .filter(merged_from.is_null().or(action.eq(HistoryAction::Merge.as_str())))
+        .distinct_on(glossary_id)
+        .order((glossary_id, revision.desc(), created_at.desc()))
+        .select((glossary_id, who))
+        .load::<(Uuid, Option<String>)>(conn)?;
+
+    Ok(editors.into_iter().collect())
+}
+
+/// One page of the history of a glossary, newest revision first
+pub fn list_glossary_history_page(
+    conn: &mut PgConnection,
+    _glossary_id: Uuid,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<GlossaryHistoryDB>, Error> {
+    use crate::schema::glossary_history::dsl::*;
+
+    // Make sure the glossary exists, so an unknown id is a 404 NOT FOUND
+    // rather than an empty history
+    glossary::table
+        .find(_glossary_id)
+        .select(glossary::id)
+        .first::<Uuid>(conn)?;
+
+    glossary_history
+        .filter(glossary_id.eq(_glossary_id))
+        .order((revision.desc(), created_at.desc()))
+        .limit(limit)
+        .offset(offset)
+        .load::<GlossaryHistoryDB>(conn)
+}
+
+/// Number of revisions in the history of a glossary
+pub fn count_glossary_history(conn: &mut PgConnection, _glossary_id: Uuid) -> Result<i64, Error> {
+    use crate::schema::glossary_history::dsl::*;
+
+    glossary_history
+        .filter(glossary_id.eq(_glossary_id))
+        .count()
+        .get_result(conn)
+}
+
+pub fn get_glossary_revision(
+    conn: &mut PgConnection,
+    _glossary_id: Uuid,
+    _revision: i32,
+) -> Result<GlossaryHistoryDB, Error> {
+    use crate::schema::glossary_history::dsl::*;
+
+    glossary_history
+        .filter(glossary_id.eq(_glossary_id))
+        .filter(revision.eq(_revision))
+        // The revisions copied over by a merge are not revisions of this glossary
+        .filter(merged_from.is_null().or(action.eq(HistoryAction::Merge.as_str())))
+        .order(created_at.desc())
+        .first::<GlossaryHistoryDB>(conn)
+}
+
+#[derive(Deserialize)]
+pub struct DiffQuery {
+    pub from: i32,
+    pub to: i32,
+}
+
+#[derive(Deserialize)]
+pub struct HistoryQuery {
+    pub limit: Option<u8>,
+    pub offset: Option<u32>,
+}
+
+/// List the revisions of a glossary `/glossary/{id}/history?limit=20&offset=0`
+#[get("/glossary/{glossary_id}/history")]
+pub async fn list(
+    id: web::Path<String>,
+    query: web::Query<HistoryQuery>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+
+    let glossary_id = Uuid::from_str(&id)
+        .map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+    let limit = query.limit.unwrap_or(20).min(100) as i64;
+    let offset = query.offset.unwrap_or(0) as i64;
+
+    let (histories, total) = web::block(move || {
+        let histories = list_glossary_history_page(&mut conn, glossary_id, limit, offset)?;
+        let total = count_glossary_history(&mut conn, glossary_id)?;
+        Ok::<_, Error>((histories, total))
+    })
+    .await??;
+
+    let revisions: Vec<GlossaryRevision> =
+        histories.iter().map(|h| h.to_glossary_revision()).collect();
+    Ok(web::Json(GlossaryRevisions::from(&revisions).with_total(total)))
+}
+
+/// Get one revision of a glossary `/glossary/{id}/history/{revision}`
+#[get("/glossary/{glossary_id}/history/{revision}")]
+pub async fn get(
+    path: web::Path<(String, i32)>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let (id, revision) = path.into_inner();
+
+    let glossary_id = Uuid::from_str(&id)
+        .map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+
+    let history =
+        web::block(move || get_glossary_revision(&mut conn, glossary_id, revision)).await??;
+    Ok(web::Json(history.to_glossary_revision()))
+}
+
+/// Compare two revisions of a glossary `/glossary/{id}/diff?from=1&to=2`
+#[get("/glossary/{glossary_id}/diff")]
+pub async fn diff(
+    id: web::Path<String>,
+    query: web::Query<DiffQuery>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+
+    let glossary_id = Uuid::from_str(&id)
+        .map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+    let (from, to) = (query.from, query.to);
+
+    let (from, to) = web::block(move || {
+        let from = get_glossary_revision(&mut conn, glossary_id, from)?;
+        let to = get_glossary_revision(&mut conn, glossary_id, to)?;
+        Ok::<_, Error>((from, to))
+    })
+    .await??;
+
+    Ok(web::Json(GlossaryDiff::new(
+        from.to_glossary_revision(),
+        to.to_glossary_revision(),
+    )))
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{service_should_ok_and_return_json, TestContext};
+    use crate::v1::glossary::{self, Glossary, GlossaryRequest};
+    use actix_web::{http, test, App};
+
+    // Create a glossary, update it twice. The history should list
+    // 3 revisions, newest first, and each revision can be fetched.
+    #[actix_rt::test]
+    async fn list_history_after_updates() {
+        let ctx = TestContext::new("list_history_after_updates");
+        let pool = ctx.get_pool();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(glossary::create)
+                .service(glossary::update)
+                .service(list)
+                .service(get),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/glossary")
+            .insert_header((crate::AUTHENTICATED_USER_HEADER, "alice@example.com"))
+            .set_json(GlossaryRequest {
+                term: Some("test_term".to_string()),
+                definition: Some("definition 0".to_string()),
+                ..Default::default()
+            });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let created: Glossary = test::read_body_json(resp).await;
+
+        for i in 1..=2 {
+            let req = test::TestRequest::put()
+                .uri(&format!("/glossary/{}", created.id))
+                .insert_header((crate::AUTHENTICATED_USER_HEADER, "bob@example.com"))
+                .set_json(GlossaryRequest {
+                    term: Some("test_term".to_string()),
+                    definition: Some(format!("definition {}", i)),
+                    ..Default::default()
+                });
+            let _ = service_should_ok_and_return_json!(app, req);
+        }
+
+        let req = test::TestRequest::get().uri(&format!("/glossary/{}/history", created.id));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let history: GlossaryRevisions = test::read_body_json(resp).await;
+        assert_eq!(history.count, 3);
+        assert_eq!(history.results[0].revision, 2);
+        assert_eq!(history.results[2].revision, 0);
+        assert_eq!(history.results[2].who, Some("alice@example.com".to_string()));
+        assert_eq!(history.results[2].action, HistoryAction::Create);
+        assert_eq!(history.results[0].action, HistoryAction::Update);
+
+        // Pagination
+        let req = test::TestRequest::get()
+            .uri(&format!("/glossary/{}/history?limit=1&offset=1", created.id));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let history: GlossaryRevisions = test::read_body_json(resp).await;
+        assert_eq!(history.count, 1);
+        assert_eq!(history.total, Some(3));
+        assert_eq!(history.results[0].revision, 1);
+
+        // Single revision
+        let req = test::TestRequest::get().uri(&format!("/glossary/{}/history/1", created.id));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let revision: GlossaryRevision = test::read_body_json(resp).await;
+        assert_eq!(revision.definition, "definition 1");
+        assert_eq!(revision.who, Some("bob@example.com".to_string()));
+    }
+
+    // Saving a glossary as it is saves no revision, even with a summary.
+    // The summary of each edit is listed with its revision.
+    #[actix_rt::test]
+    async fn skip_noop_edits_and_keep_summaries() {
+        let ctx = TestContext::new("skip_noop_edits_and_keep_summaries");
+        let pool = ctx.get_pool();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(glossary::create)
+                .service(glossary::update)
+                .service(list),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/glossary")
+            .set_json(GlossaryRequest {
+                term: Some("test_term".to_string()),
+                definition: Some("definition 0".to_string()),
+                summary: Some("First draft".to_string()),
+                ..Default::default()
+            });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let created: Glossary = test::read_body_json(resp).await;
+
+        let edits = [
+            ("definition 0", None, 0),
+            ("definition 0", Some("Nothing to see"), 0),
+            ("definition 1", Some("Fix the definition"), 1),
+        ];
+        for (definition, summary, revision) in edits {
+            let req = test::TestRequest::put()
+                .uri(&format!("/glossary/{}", created.id))
+                .set_json(GlossaryRequest {
+                    term: Some("test_term".to_string()),
+                    definition: Some(definition.to_string()),
+                    summary: summary.map(str::to_string),
+                    ..Default::default()
+                });
+            let resp = service_should_ok_and_return_json!(app, req);
+            let updated: Glossary = test::read_body_json(resp).await;
+            assert_eq!(updated.revision, revision);
+        }
+
+        let req = test::TestRequest::get().uri(&format!("/glossary/{}/history", created.id));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let history: GlossaryRevisions = test::read_body_json(resp).await;
+        let summaries: Vec<Option<String>> =
+            history.results.into_iter().map(|r| r.summary).collect();
+        assert_eq!(
+            summaries,
+            vec![
+                Some("Fix the definition".to_string()),
+                Some("First draft".to_string())
+            ]
+        );
+    }
+
+    fn revision(revision: i32, term: &str, definition: &str) -> GlossaryRevision {
+        GlossaryRevision {
+            revision,
+            action: HistoryAction::Update,
+            term: term.to_string(),
+            domain: None,
+            abbreviation: None,
+            expansion: None,
+            status: GlossaryStatus::Active,
+            status_reason: None,
+            replaced_by: None,
+            definition: definition.to_string(),
+            who: None,
+            created_at: Utc::now(),
+            reverted_from: None,
+            merged_from: None,
+            summary: None,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn diff_definition_by_words() {
+        let changes = GlossaryDiff::new(
+            revision(1, "k8s", "A container orchestrator by Google"),
+            revision(2, "Kubernetes", "A container orchestrator by the CNCF"),
+        );
+
+        assert!(changes.term_changed);
+        assert_eq!(
+            changes.definition,
+            vec![
+                DiffSegment {
+                    op: DiffOp::Equal,
+                    text: "A container orchestrator by ".to_string(),
+                },
+                DiffSegment {
+                    op: DiffOp::Delete,
+                    text: "Google".to_string(),
+                },
+                DiffSegment {
+                    op: DiffOp::Insert,
+                    text: "the CNCF".to_string(),
+                },
+            ]
+        );
+        assert!(changes.unified.starts_with("--- revision 1\n+++ revision 2\n"));
+        assert!(changes.unified.contains("-k8s\n+Kubernetes\n"));
+    }
+
+    #[actix_rt::test]
+    async fn diff_same_revision() {
+        let changes = GlossaryDiff::new(
+            revision(1, "k8s", "Kubernetes"),
+            revision(1, "k8s", "Kubernetes"),
+        );
+
+        assert!(!changes.term_changed);
+        assert_eq!(changes.definition.len(), 1);
+        assert_eq!(changes.definition[0].op, DiffOp::Equal);
+        assert!(changes.unified.is_empty());
+    }
+
+    // Diff two revisions using the API
+    #[actix_rt::test]
+    async fn diff_revisions() {
+        let ctx = TestContext::new("diff_revisions");
+        let pool = ctx.get_pool();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(glossary::create)
+                .service(glossary::update)
+                .service(diff),
+        )
+        .await;
+
+        let req = test::TestRequest::post().uri("/glossary").set_json(GlossaryRequest {
+            term: Some("SLA".to_string()),
+            definition: Some("Service level agreement".to_string()),
+            ..Default::default()
+        });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let created: Glossary = test::read_body_json(resp).await;
+
+        let req = test::TestRequest::put()
+            .uri(&format!("/glossary/{}", created.id))
+            .set_json(GlossaryRequest {
+                term: Some("SLA".to_string()),
+                definition: Some("Service level agreement with customers".to_string()),
+                ..Default::default()
+            });
+        let _ = service_should_ok_and_return_json!(app, req);
+
+        let req =
+            test::TestRequest::get().uri(&format!("/glossary/{}/diff?from=0&to=1", created.id));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let changes: GlossaryDiff = test::read_body_json(resp).await;
+        assert!(!changes.term_changed);
+        assert_eq!(changes.definition.last().unwrap().op, DiffOp::Insert);
+        assert_eq!(changes.definition.last().unwrap().text, " with customers");
+
+        // Unknown revision
+        let req = test::TestRequest::get()
+            .uri(&format!("/glossary/{}/diff?from=0&to=5", created.id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    // History of an unknown glossary or revision should return 404 NOT FOUND
+    #[actix_rt::test]
+    async fn history_not_found() {
+        let ctx = TestContext::new("history_not_found");
+        let pool = ctx.get_pool();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(list)
+                .service(get),
+        )
+        .await;
+
+        let non_exists_id = Uuid::new_v4();
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/glossary/{}/history", non_exists_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/glossary/{}/history/3", non_exists_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+    }
+}
@@ -5,9 +5,11 @@ use diesel::{
     RunQueryDsl,
 };
 use serde::{Deserialize, Serialize};
-use std::str::FromStr;
+use std::{collections::HashSet, str::FromStr};
 use uuid::Uuid;
 
+use super::glossary::get_glossary;
+use super::reaction::{reactor, ReactionKind};
 use crate::{
     response::{ApiError, ListResp, Message},
     schema::*,
@@ -44,6 +46,7 @@ impl Like {
             created_at: self.created_at.naive_utc(),
             glossary_id,
             who: self.who.clone(),
+            kind: ReactionKind::Helpful.as_str().to_string(),
         }
     }
 }
@@ -55,6 +58,7 @@ pub struct LikeDB {
     pub created_at: NaiveDateTime,
     pub glossary_id: Uuid,
     pub who: Option<String>,
+    pub kind: String,
 }
 
 impl LikeDB {
@@ -72,6 +76,7 @@ pub fn list_likes(conn: &mut PgConnection, _glossary_id: Uuid) -> Result<Vec<Lik
 
     match likes
         .filter(glossary_id.eq(_glossary_id))
+        .filter(kind.eq(ReactionKind::Helpful.as_str()))
         .order(created_at.desc())
         .load::<LikeDB>(conn)
     {
@@ -80,48 +85,64 @@ pub fn list_likes(conn: &mut PgConnection, _glossary_id: Uuid) -> Result<Vec<Lik
     }
 }
 
+/// Like a glossary as `_who`. Liking twice keeps the first like.
 pub fn create_like(
     conn: &mut PgConnection,
     _glossary_id: Uuid,
-    _who: Option<String>,
+    _who: &str,
 ) -> Result<Like, Error> {
     use crate::schema::likes::dsl::*;
 
-    let like = Like::new(_who);
+    // Glossaries in the trash can not be liked
+    get_glossary(conn, _glossary_id)?;
+
+    let like = Like::new(Some(_who.to_string()));
 
     diesel::insert_into(likes)
         .values(&like.to_like_db(_glossary_id))
+        .on_conflict((glossary_id, who, kind))
+        .do_nothing()
         .execute(conn)?;
 
-    Ok(like)
+    likes
+        .filter(glossary_id.eq(_glossary_id))
+        .filter(who.eq(_who))
+        .filter(kind.eq(ReactionKind::Helpful.as_str()))
+        .first::<LikeDB>(conn)
+        .map(|l| l.to_like())
 }
 
-pub fn delete_one_like(
+/// Remove the like of `_who` from a glossary, if any
+pub fn delete_like(
     conn: &mut PgConnection,
     _glossary_id: Uuid,
-    _like_id: Option<Uuid>,
-) -> Result<(), Error> {
+    _who: &str,
+) -> Result<usize, Error> {
     use crate::schema::likes::dsl::*;
 
-    let like: Option<Like> = if let Some(like_id) = _like_id {
-        match likes.filter(id.eq(like_id)).load::<LikeDB>(conn) {
-            Ok(lks) => lks.first().map(|v| v.to_like()),
-            _ => None,
-        }
-    } else {
-        match list_likes(conn, _glossary_id) {
-            Ok(_likes) if !_likes.is_empty() => _likes.first().cloned(),
-            _ => None,
-        }
-    };
+    let target = likes
+        .filter(glossary_id.eq(_glossary_id))
+        .filter(who.eq(_who))
+        .filter(kind.eq(ReactionKind::Helpful.as_str()));
+    diesel::delete(target).execute(conn)
+}
 
-    if like.is_none() {
-        return Ok(());
-    }
+/// The glossaries among `glossary_ids` liked by `_who`
+pub fn list_liked_by(
+    conn: &mut PgConnection,
+    glossary_ids: &[Uuid],
+    _who: &str,
+) -> Result<HashSet<Uuid>, Error> {
+    use crate::schema::likes::dsl::*;
+
+    let liked = likes
+        .filter(glossary_id.eq_any(glossary_ids))
+        .filter(who.eq(_who))
+        .filter(kind.eq(ReactionKind::Helpful.as_str()))
+        .select(glossary_id)
+        .load::<Uuid>(conn)?;
 
-    let like_id = Uuid::from_str(like.unwrap().id.as_str()).unwrap();
-    diesel::delete(likes.filter(id.eq(like_id))).execute(conn)?;
-    Ok(())
+    Ok(liked.into_iter().collect())
 }
 
 /// List likes for a glossary id
@@ -139,39 +160,35 @@ pub async fn list(
     Ok(web::Json(Likes::from(&likes)))
 }
 
-/// Add one like to a glossary `/glossary/{id}/likes`
+/// Like a glossary `/glossary/{id}/likes`, once per user
 #[post("/glossary/{glossary_id}/likes")]
 pub async fn plus_one(
     id: web::Path<String>,
     pool: web::Data<DBPool>,
     req: HttpRequest,
 ) -> actix_web::Result<impl Responder, ApiError> {
-    let mut conn = pool.get().expect("could not get db connection from pool");
-
-    let who = req
-        .headers()
-        .get(crate::AUTHENTICATED_USER_HEADER)
-        .map(|email| email.to_str().unwrap().to_string());
-
     let glossary_id = Uuid::from_str(&id)
         .map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+    let who = reactor(&req)?;
 
-    let like = web::block(move || create_like(&mut conn, glossary_id, who)).await??;
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let like = web::block(move || create_like(&mut conn, glossary_id, &who)).await??;
     Ok(web::Json(like))
 }
 
-/// Delete one like from a glossary `/glossary/{glossary_id}/likes`
+/// Remove the caller's like from a glossary `/glossary/{glossary_id}/likes`
 #[delete("/glossary/{glossary_id}/likes")]
 pub async fn minus_one(
     id: web::Path<String>,
     pool: web::Data<DBPool>,
+    req: HttpRequest,
 ) -> actix_web::Result<impl Responder, ApiError> {
-    let mut conn = pool.get().expect("could not get db connection from pool");
-
     let glossary_id = Uuid::from_str(&id)
         .map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+    let who = reactor(&req)?;
 
-    web::block(move || delete_one_like(&mut conn, glossary_id, None)).await??;
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    web::block(move || delete_like(&mut conn, glossary_id, &who)).await??;
     Ok(web::Json(Message::new("ok")))
 }
 
@@ -179,28 +196,12 @@ pub async fn minus_one(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::TestContext;
+    use crate::test_utils::{service_should_ok_and_return_json, TestContext};
     use crate::v1::glossary::GlossaryDB;
     use actix_web::{http, test, App};
     use chrono::Utc;
     use uuid::Uuid;
 
-    macro_rules! service_should_ok_and_return_json {
-        ($app:expr, $req:expr) => {{
-            let req = test::TestRequest::from($req).to_request();
-            let resp = test::call_service(&$app, req).await;
-            println!("Debug: Resp = {:?}", resp);
-
-            assert!(resp.status().is_success());
-            assert_eq!(
-                resp.headers().get("content-type").unwrap(),
-                "application/json"
-            );
-
-            resp
-        }};
-    }
-
     // Using the list likes to get the list of non-exist glossary.
     // The count should be 0.
     #[actix_rt::test]
@@ -295,6 +296,7 @@ mod tests {
             revision: 1,
             created_at: Utc::now().naive_utc(),
             updated_at: Utc::now().naive_utc(),
+            ..Default::default()
         };
 
         // Insert glossary item into database
@@ -340,6 +342,7 @@ mod tests {
             definition: "test_definition_1".to_string(),
             created_at: Utc::now().naive_utc(),
             updated_at: Utc::now().naive_utc(),
+            ..Default::default()
         };
 
         // Insert to glossaries
@@ -360,6 +363,7 @@ mod tests {
         // Create a like using api POST /glossary/{glossary_id}/likes
         let req = test::TestRequest::post()
             .uri(&format!("/glossary/{}/likes", glossary_id))
+            .insert_header((crate::AUTHENTICATED_USER_HEADER, "reader@example.com"))
             .set_json(b"{}");
         let _ = service_should_ok_and_return_json!(app, req);
 
@@ -373,7 +377,7 @@ mod tests {
     }
 
     // Using the plus_one to create a like for non-existent glossary
-    // Should return 409 CONFLICT (foreign key constraint violation)
+    // Should return 404 NOT FOUND, as for a glossary in the trash
     #[actix_rt::test]
     async fn one_like_non_exists_glossary() {
         let ctx = TestContext::new("one_like_non_exists_glossary");
@@ -393,17 +397,17 @@ mod tests {
         // Create a like using api POST /glossary/{glossary_id}/likes
         let req = test::TestRequest::post()
             .uri(&format!("/glossary/{}/likes", non_exists_glossary_id))
+            .insert_header((crate::AUTHENTICATED_USER_HEADER, "reader@example.com"))
             .set_json(b"{}")
             .to_request();
 
         let resp = test::call_service(&app, req).await;
-        // Foreign key violation returns 409 CONFLICT
-        assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+        assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
     }
 
     // Insert a glossary into database. Using the plus_one to create a like.
-    // Using the plus_one to create a like.
-    // Using the list likes to get the list of likes. The count should be 2.
+    // Using the plus_one to create a like again as the same user.
+    // Using the list likes to get the list of likes. The count should be 1.
     #[actix_rt::test]
     async fn like_two_times() {
         use crate::schema::glossary;
@@ -421,6 +425,7 @@ mod tests {
             definition: "test_definition_1".to_string(),
             created_at: Utc::now().naive_utc(),
             updated_at: Utc::now().naive_utc(),
+            ..Default::default()
         };
 
         // Insert two glossaries
@@ -439,18 +444,22 @@ mod tests {
         .await;
 
         // Create the fist like using api POST /glossary/{glossary_id}/likes
-        let req = test::TestRequest::post().uri(&format!("/glossary/{}/likes", glossary_id));
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/likes", glossary_id))
+            .insert_header((crate::AUTHENTICATED_USER_HEADER, "reader@example.com"));
         let _ = service_should_ok_and_return_json!(app, req);
 
-        // Create the 2nd like using api POST /glossary/{glossary_id}/likes
-        let req = test::TestRequest::post().uri(&format!("/glossary/{}/likes", glossary_id));
+        // Like again using api POST /glossary/{glossary_id}/likes
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/likes", glossary_id))
+            .insert_header((crate::AUTHENTICATED_USER_HEADER, "reader@example.com"));
         let _ = service_should_ok_and_return_json!(app, req);
 
         // Get the list of likes using GET /glossary/{glossary_id}/likes
         let req = test::TestRequest::get().uri(&format!("/glossary/{}/likes", glossary_id));
         let resp = service_should_ok_and_return_json!(app, req);
         let response: Likes = test::read_body_json(resp).await;
-        assert_eq!(response.count, 2);
+        assert_eq!(response.count, 1);
     }
 
     // Insert a glossary into database.
@@ -475,6 +484,7 @@ mod tests {
             definition: "test_definition_1".to_string(),
             created_at: Utc::now().naive_utc(),
             updated_at: Utc::now().naive_utc(),
+            ..Default::default()
         };
 
         // Insert two glossaries
@@ -493,7 +503,9 @@ mod tests {
         .await;
 
         // Create a like using api POST /glossary/{glossary_id}/likes
-        let req = test::TestRequest::post().uri(&format!("/glossary/{}/likes", glossary_id));
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/likes", glossary_id))
+            .insert_header((crate::AUTHENTICATED_USER_HEADER, "reader@example.com"));
         let _ = service_should_ok_and_return_json!(app, req);
 
         // Get the list of likes using GET /glossary/{glossary_id}/likes
@@ -504,7 +516,9 @@ mod tests {
         assert_eq!(likes.count, 1);
 
         // Delete the like using api DELETE /glossary/{glossary_id}/likes
-        let req = test::TestRequest::delete().uri(&format!("/glossary/{}/likes", glossary_id));
+        let req = test::TestRequest::delete()
+            .uri(&format!("/glossary/{}/likes", glossary_id))
+            .insert_header((crate::AUTHENTICATED_USER_HEADER, "reader@example.com"));
         let _ = service_should_ok_and_return_json!(app, req);
 
         // Get the list of likes using GET /glossary/{glossary_id}/likes
@@ -514,4 +528,77 @@ mod tests {
         let likes: Likes = test::read_body_json(resp).await;
         assert_eq!(likes.count, 0);
     }
+
+    // Anonymous likes are rejected with 401 UNAUTHORIZED
+    #[actix_rt::test]
+    async fn anonymous_like() {
+        let ctx = TestContext::new("anonymous_like");
+        let pool = ctx.get_pool();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(plus_one)
+                .service(minus_one),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/likes", Uuid::new_v4()))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::UNAUTHORIZED);
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/glossary/{}/likes", Uuid::new_v4()))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::UNAUTHORIZED);
+    }
+
+    // Two users like a glossary. One of them unlikes it twice,
+    // the like of the other user is kept.
+    #[actix_rt::test]
+    async fn unlike_only_own_like() {
+        use crate::schema::glossary;
+
+        let ctx = TestContext::new("unlike_only_own_like");
+        let pool = ctx.get_pool();
+        let conn = &mut pool.get().expect("could not get db connection from pool");
+
+        let glossary_id = Uuid::new_v4();
+        diesel::insert_into(glossary::table)
+            .values(GlossaryDB {
+                id: glossary_id,
+                term: "test_term_1".to_string(),
+                definition: "test_definition_1".to_string(),
+                ..Default::default()
+            })
+            .execute(conn)
+            .expect("could not insert glossary");
+
+        create_like(conn, glossary_id, "alice@example.com").unwrap();
+        create_like(conn, glossary_id, "bob@example.com").unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(list)
+                .service(minus_one),
+        )
+        .await;
+
+        for _ in 0..2 {
+            let req = test::TestRequest::delete()
+                .uri(&format!("/glossary/{}/likes", glossary_id))
+                .insert_header((crate::AUTHENTICATED_USER_HEADER, "bob@example.com"));
+            let _ = service_should_ok_and_return_json!(app, req);
+        }
+
+        let req = test::TestRequest::get().uri(&format!("/glossary/{}/likes", glossary_id));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let likes: Likes = test::read_body_json(resp).await;
+        assert_eq!(likes.count, 1);
+        assert_eq!(likes.results[0].who.as_deref(), Some("alice@example.com"));
+    }
 }
@@ -1,4 +1,12 @@
+pub mod acronym;
+pub mod alias;
 pub mod glossary;
 pub mod glossary_history;
 pub mod health;
 pub mod like;
+pub mod reaction;
+pub mod relation;
+pub mod tag;
+pub mod tombstone;
+pub mod translation;
+pub mod trash;
@@ -0,0 +1,358 @@
+use actix_web::{delete, get, post, web, HttpRequest, Responder};
+use diesel::{
+    dsl::count_star, pg::PgConnection, result::Error, ExpressionMethods, QueryDsl, RunQueryDsl,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, str::FromStr};
+use uuid::Uuid;
+
+use super::glossary::{get_glossary, list_popular_glossary, PopularMode, PopularWindow};
+use crate::{response::ApiError, schema::*, DBPool};
+
+/// What a reader thinks of a glossary: 👍 helpful, ❓ unclear or ⚠️ outdated.
+/// A like is a `Helpful` reaction.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Eq, PartialEq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ReactionKind {
+    Helpful,
+    Unclear,
+    Outdated,
+}
+
+impl ReactionKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReactionKind::Helpful => "helpful",
+            ReactionKind::Unclear => "unclear",
+            ReactionKind::Outdated => "outdated",
+        }
+    }
+}
+
+impl FromStr for ReactionKind {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "helpful" => Ok(ReactionKind::Helpful),
+            "unclear" => Ok(ReactionKind::Unclear),
+            "outdated" => Ok(ReactionKind::Outdated),
+            _ => Err(ApiError::invalid_input(
+                "Reaction must be one of helpful, unclear, outdated",
+            )),
+        }
+    }
+}
+
+/// Number of reactions of each kind on a glossary
+#[derive(Debug, Clone, Default, Deserialize, Serialize, Eq, PartialEq)]
+pub struct ReactionCounts {
+    pub helpful: i32,
+    pub unclear: i32,
+    pub outdated: i32,
+}
+
+impl ReactionCounts {
+    fn add(&mut self, kind: ReactionKind, count: i32) {
+        match kind {
+            ReactionKind::Helpful => self.helpful += count,
+            ReactionKind::Unclear => self.unclear += count,
+            ReactionKind::Outdated => self.outdated += count,
+        }
+    }
+}
+
+/// React to a glossary as `_who`. Reacting twice with the same kind keeps the first reaction.
+pub fn create_reaction(
+    conn: &mut PgConnection,
+    _glossary_id: Uuid,
+    _who: &str,
+    _kind: ReactionKind,
+) -> Result<usize, Error> {
+    use crate::schema::likes::dsl::*;
+
+    // Glossaries in the trash can not get new reactions
+    get_glossary(conn, _glossary_id)?;
+
+    diesel::insert_into(likes)
+        .values((
+            id.eq(Uuid::new_v4()),
+            glossary_id.eq(_glossary_id),
+            who.eq(_who),
+            kind.eq(_kind.as_str()),
+        ))
+        .on_conflict((glossary_id, who, kind))
+        .do_nothing()
+        .execute(conn)
+}
+
+/// Remove the reaction of `_who` of this kind from a glossary, if any
+pub fn delete_reaction(
+    conn: &mut PgConnection,
+    _glossary_id: Uuid,
+    _who: &str,
+    _kind: ReactionKind,
+) -> Result<usize, Error> {
+    use crate::schema::likes::dsl::*;
+
+    let target = likes
+        .filter(glossary_id.eq(_glossary_id))
+        .filter(who.eq(_who))
+        .filter(kind.eq(_kind.as_str()));
+    diesel::delete(target).execute(conn)
+}
+
+/// Count the reactions of several glossaries at once, glossaries without reactions are absent
+pub fn count_reactions_by_glossary(
+    conn: &mut PgConnection,
+    glossary_ids: &[Uuid],
+) -> Result<HashMap<Uuid, ReactionCounts>, Error> {
+    let rows = likes::table
+        .filter(likes::glossary_id.eq_any(glossary_ids))
+        .group_by((likes::glossary_id, likes::kind))
+        .select((likes::glossary_id, likes::kind, count_star()))
+        .load::<(Uuid, String, i64)>(conn)?;
+
+    let mut counts_by_glossary: HashMap<Uuid, ReactionCounts> = HashMap::new();
+    for (glossary_id, kind, count) in rows {
+        // The kinds are enforced by a CHECK constraint
+        if let Ok(kind) = ReactionKind::from_str(&kind) {
+            counts_by_glossary
+                .entry(glossary_id)
+                .or_default()
+                .add(kind, count as i32);
+        }
+    }
+
+    Ok(counts_by_glossary)
+}
+
+/// The authenticated user, anonymous reactions are rejected
+pub fn reactor(req: &HttpRequest) -> Result<String, ApiError> {
+    req.headers()
+        .get(crate::AUTHENTICATED_USER_HEADER)
+        .and_then(|email| email.to_str().ok())
+        .filter(|email| !email.trim().is_empty())
+        .map(|email| email.to_string())
+        .ok_or_else(|| ApiError::unauthorized("Sign in to react to a glossary"))
+}
+
+/// Parse the glossary id and the reaction kind of `/glossary/{id}/reactions/{kind}`
+fn parse_reaction_path(path: (String, String)) -> Result<(Uuid, ReactionKind), ApiError> {
+    let (id, kind) = path;
+    let glossary_id =
+        Uuid::from_str(&id).map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+
+    Ok((glossary_id, ReactionKind::from_str(&kind)?))
+}
+
+/// React to a glossary `/glossary/{id}/reactions/{kind}`, once per user and kind.
+/// Returns the reaction counts of the glossary.
+#[post("/glossary/{glossary_id}/reactions/{kind}")]
+pub async fn react(
+    path: web::Path<(String, String)>,
+    pool: web::Data<DBPool>,
+    req: HttpRequest,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let (glossary_id, kind) = parse_reaction_path(path.into_inner())?;
+    let who = reactor(&req)?;
+
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let counts = web::block(move || {
+        create_reaction(&mut conn, glossary_id, &who, kind)?;
+        let mut counts = count_reactions_by_glossary(&mut conn, &[glossary_id])?;
+        Ok::<_, Error>(counts.remove(&glossary_id).unwrap_or_default())
+    })
+    .await??;
+
+    Ok(web::Json(counts))
+}
+
+/// Remove the caller's reaction from a glossary `/glossary/{id}/reactions/{kind}`.
+/// Returns the reaction counts of the glossary.
+#[delete("/glossary/{glossary_id}/reactions/{kind}")]
+pub async fn unreact(
+    path: web::Path<(String, String)>,
+    pool: web::Data<DBPool>,
+    req: HttpRequest,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let (glossary_id, kind) = parse_reaction_path(path.into_inner())?;
+    let who = reactor(&req)?;
+
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let counts = web::block(move || {
+        delete_reaction(&mut conn, glossary_id, &who, kind)?;
+        let mut counts = count_reactions_by_glossary(&mut conn, &[glossary_id])?;
+        Ok::<_, Error>(counts.remove(&glossary_id).unwrap_or_default())
+    })
+    .await??;
+
+    Ok(web::Json(counts))
+}
+
+#[derive(Deserialize)]
+pub struct ReportQuery {
+    pub limit: Option<u8>,
+    /// `7d`, `30d` or `all` (default)
+    pub window: Option<String>,
+}
+
+/// Glossaries with the most reactions of a kind first, `/reports/reactions/outdated?limit=20`.
+/// Lets term stewards find the definitions readers find unclear or outdated.
+#[get("/reports/reactions/{kind}")]
+pub async fn report(
+    kind: web::Path<String>,
+    query: web::Query<ReportQuery>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let kind = ReactionKind::from_str(&kind)?;
+    let limit = query.limit.unwrap_or(20);
+    let window = PopularWindow::from_str(query.window.as_deref().unwrap_or("all"))?;
+
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let glossaries = web::block(move || {
+        list_popular_glossary(&mut conn, limit, window, PopularMode::Top, kind, None)
+    })
+    .await??;
+
+    Ok(web::Json(glossaries))
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{service_should_ok_and_return_json, TestContext};
+    use crate::v1::glossary::{get, Glossary, GlossaryDB};
+    use actix_web::{http, test, App};
+
+    // React to a glossary with several kinds, twice with the same kind,
+    // then take one reaction back. The glossary carries the counts per kind.
+    #[actix_rt::test]
+    async fn react_and_unreact() {
+        let ctx = TestContext::new("react_and_unreact");
+        let pool = ctx.get_pool();
+        let conn = &mut pool.get().expect("could not get db connection from pool");
+
+        let glossary_id = Uuid::new_v4();
+        diesel::insert_into(glossary::table)
+            .values(GlossaryDB {
+                id: glossary_id,
+                term: "test_term".to_string(),
+                definition: "test_definition".to_string(),
+                ..Default::default()
+            })
+            .execute(conn)
+            .expect("could not insert glossary");
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(react)
+                .service(unreact)
+                .service(get),
+        )
+        .await;
+
+        for (who, kind) in [
+            ("alice@example.com", "helpful"),
+            ("alice@example.com", "unclear"),
+            ("alice@example.com", "unclear"),
+            ("bob@example.com", "unclear"),
+            ("bob@example.com", "outdated"),
+        ] {
+            let req = test::TestRequest::post()
+                .uri(&format!("/glossary/{}/reactions/{}", glossary_id, kind))
+                .insert_header((crate::AUTHENTICATED_USER_HEADER, who));
+            let _ = service_should_ok_and_return_json!(app, req);
+        }
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/glossary/{}/reactions/outdated", glossary_id))
+            .insert_header((crate::AUTHENTICATED_USER_HEADER, "bob@example.com"));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let counts: ReactionCounts = test::read_body_json(resp).await;
+        assert_eq!(
+            counts,
+            ReactionCounts {
+                helpful: 1,
+                unclear: 2,
+                outdated: 0,
+            }
+        );
+
+        // The likes count is the number of helpful reactions
+        let req = test::TestRequest::get().uri(&format!("/glossary/{}", glossary_id));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let glossary: Glossary = test::read_body_json(resp).await;
+        assert_eq!(glossary.reactions, counts);
+        assert_eq!(glossary.likes_count, 1);
+
+        // Unknown kind
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/reactions/angry", glossary_id))
+            .insert_header((crate::AUTHENTICATED_USER_HEADER, "bob@example.com"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+
+        // Anonymous reaction
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/reactions/unclear", glossary_id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::UNAUTHORIZED);
+
+        // Glossaries in the trash can not get new reactions
+        diesel::update(glossary::table.find(glossary_id))
+            .set(glossary::deleted_at.eq(Some(chrono::Utc::now().naive_utc())))
+            .execute(conn)
+            .unwrap();
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/reactions/unclear", glossary_id))
+            .insert_header((crate::AUTHENTICATED_USER_HEADER, "carol@example.com"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    // The report lists the glossaries with the most reactions of a kind first
+    #[actix_rt::test]
+    async fn report_by_kind() {
+        let ctx = TestContext::new("report_by_kind");
+        let pool = ctx.get_pool();
+        let conn = &mut pool.get().expect("could not get db connection from pool");
+
+        let mut ids = vec![];
+        for term in ["a_term", "b_term", "c_term"] {
+            let glossary_id = Uuid::new_v4();
+            diesel::insert_into(glossary::table)
+                .values(GlossaryDB {
+                    id: glossary_id,
+                    term: term.to_string(),
+                    definition: "test_definition".to_string(),
+                    ..Default::default()
+                })
+                .execute(conn)
+                .expect("could not insert glossary");
+            ids.push(glossary_id);
+        }
+
+        // b_term is outdated for 2 readers, a_term for 1 and c_term is only helpful
+        create_reaction(conn, ids[0], "alice@example.com", ReactionKind::Outdated).unwrap();
+        create_reaction(conn, ids[1], "alice@example.com", ReactionKind::Outdated).unwrap();
+        create_reaction(conn, ids[1], "bob@example.com", ReactionKind::Outdated).unwrap();
+        create_reaction(conn, ids[2], "bob@example.com", ReactionKind::Helpful).unwrap();
+
+        let app =
+            test::init_service(App::new().app_data(web::Data::new(pool)).service(report)).await;
+
+        let req = test::TestRequest::get().uri("/reports/reactions/outdated");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let glossaries: Vec<Glossary> = test::read_body_json(resp).await;
+        let terms: Vec<&str> = glossaries.iter().map(|g| g.term.as_str()).collect();
+        assert_eq!(terms, vec!["b_term", "a_term"]);
+        assert_eq!(glossaries[0].reactions.outdated, 2);
+        assert_eq!(glossaries[0].likes_count, 0);
+    }
+}
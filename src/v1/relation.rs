@@ -0,0 +1,493 @@
+use actix_web::{delete, get, post, web, Responder};
+use chrono::Utc;
+use diesel::{
+    pg::PgConnection, result::Error, sql_query, sql_types::Bool, BoolExpressionMethods,
+    Connection, ExpressionMethods, JoinOnDsl, QueryDsl, QueryableByName, RunQueryDsl,
+};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use uuid::Uuid;
+
+use super::glossary::get_glossary;
+use crate::{
+    response::{ApiError, Message},
+    schema::*,
+    DBPool,
+};
+
+/// How a glossary relates to another one
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RelationKind {
+    /// See also
+    Related,
+    /// The other glossary is more general
+    Broader,
+    /// The other glossary is more specific
+    Narrower,
+    /// The other glossary means the opposite
+    Antonym,
+    /// The other glossary should be used instead
+    ReplacedBy,
+}
+
+impl RelationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RelationKind::Related => "related",
+            RelationKind::Broader => "broader",
+            RelationKind::Narrower => "narrower",
+            RelationKind::Antonym => "antonym",
+            RelationKind::ReplacedBy => "replaced-by",
+        }
+    }
+
+    /// The relation stored the other way around, if any
+    pub fn inverse(&self) -> Option<RelationKind> {
+        match self {
+            RelationKind::Related => Some(RelationKind::Related),
+            RelationKind::Broader => Some(RelationKind::Narrower),
+            RelationKind::Narrower => Some(RelationKind::Broader),
+            RelationKind::Antonym => Some(RelationKind::Antonym),
+            RelationKind::ReplacedBy => None,
+        }
+    }
+}
+
+impl FromStr for RelationKind {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "related" => Ok(RelationKind::Related),
+            "broader" => Ok(RelationKind::Broader),
+            "narrower" => Ok(RelationKind::Narrower),
+            "antonym" => Ok(RelationKind::Antonym),
+            "replaced-by" => Ok(RelationKind::ReplacedBy),
+            _ => Err(ApiError::invalid_input(
+                "Relation must be one of related, broader, narrower, antonym, replaced-by",
+            )),
+        }
+    }
+}
+
+/// A relation to another glossary, with its term resolved
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct Relation {
+    pub relation: RelationKind,
+    pub id: String,
+    pub term: String,
+}
+
+#[derive(QueryableByName)]
+struct Found {
+    #[diesel(sql_type = Bool)]
+    found: bool,
+}
+
+/// Whether `ancestor` is `_id` or one of its broader glossaries, transitively.
+/// The glossaries in the trash are left out, as in `list_relations`.
+fn is_ancestor(conn: &mut PgConnection, _id: Uuid, ancestor: Uuid) -> Result<bool, Error> {
+    if _id == ancestor {
+        return Ok(true);
+    }
+
+    sql_query(
+        "WITH RECURSIVE ancestors(id) AS (
+             SELECT target_id FROM glossary_relations
+             INNER JOIN glossary ON glossary.id = glossary_relations.target_id
+             WHERE source_id = $1 AND relation = 'broader' AND glossary.deleted_at IS NULL
+             UNION
+             SELECT glossary_relations.target_id FROM glossary_relations
+             INNER JOIN ancestors ON glossary_relations.source_id = ancestors.id
+             INNER JOIN glossary ON glossary.id = glossary_relations.target_id
+             WHERE glossary_relations.relation = 'broader' AND glossary.deleted_at IS NULL
+         )
+         SELECT EXISTS (SELECT 1 FROM ancestors WHERE id = $2) AS found",
+    )
+    .bind::<diesel::sql_types::Uuid, _>(_id)
+    .bind::<diesel::sql_types::Uuid, _>(ancestor)
+    .get_result::<Found>(conn)
+    .map(|f| f.found)
+}
+
+/// Relations of a glossary to the glossaries which are not in the trash,
+/// sorted by relation then term
+pub fn list_relations(conn: &mut PgConnection, _id: Uuid) -> Result<Vec<Relation>, Error> {
+    let rows = glossary_relations::table
+        .inner_join(glossary::table.on(glossary::id.eq(glossary_relations::target_id)))
+        .filter(glossary_relations::source_id.eq(_id))
+        .filter(glossary::deleted_at.is_null())
+        .select((glossary_relations::relation, glossary::id, glossary::term))
+        .order((glossary_relations::relation.asc(), glossary::term.asc()))
+        .load::<(String, Uuid, String)>(conn)?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|(relation, id, term)| {
+            // The relations are enforced by a CHECK constraint
+            let relation = RelationKind::from_str(&relation).ok()?;
+            Some(Relation {
+                relation,
+                id: id.to_string(),
+                term,
+            })
+        })
+        .collect())
+}
+
+/// Relate a glossary to another one, and the other one back with the inverse relation.
+/// A glossary can not be broader than itself, directly or not.
+pub fn add_relation(
+    conn: &mut PgConnection,
+    source_id: Uuid,
+    kind: RelationKind,
+    target_id: Uuid,
+) -> Result<Vec<Relation>, ApiError> {
+    if source_id == target_id {
+        return Err(ApiError::invalid_input("A glossary can not relate to itself"));
+    }
+
+    conn.transaction::<_, ApiError, _>(|conn| {
+        get_glossary(conn, source_id)?;
+        get_glossary(conn, target_id)?;
+
+        insert_relation(conn, source_id, kind, target_id)?;
+
+        Ok(list_relations(conn, source_id)?)
+    })
+}
+
+/// Store a relation and its inverse, unless it makes a hierarchy cycle
+fn insert_relation(
+    conn: &mut PgConnection,
+    source_id: Uuid,
+    kind: RelationKind,
+    target_id: Uuid,
+) -> Result<(), ApiError> {
+    let hierarchy = match kind {
+        RelationKind::Broader => Some((source_id, target_id)),
+        RelationKind::Narrower => Some((target_id, source_id)),
+        _ => None,
+    };
+    if let Some((child, parent)) = hierarchy {
+        if is_ancestor(conn, parent, child)? {
+            return Err(ApiError::conflict(
+                "The relation would make a glossary broader than itself",
+            ));
+        }
+    }
+
+    let now = Utc::now().naive_utc();
+    let mut links = vec![(source_id, target_id, kind)];
+    if let Some(inverse) = kind.inverse() {
+        links.push((target_id, source_id, inverse));
+    }
+    for (source, target, relation) in links {
+        diesel::insert_into(glossary_relations::table)
+            .values((
+                glossary_relations::source_id.eq(source),
+                glossary_relations::target_id.eq(target),
+                glossary_relations::relation.eq(relation.as_str()),
+                glossary_relations::created_at.eq(now),
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn)?;
+    }
+
+    Ok(())
+}
+
+/// Remove a relation, and its inverse
+pub fn remove_relation(
+    conn: &mut PgConnection,
+    _source_id: Uuid,
+    kind: RelationKind,
+    _target_id: Uuid,
+) -> Result<usize, Error> {
+    use crate::schema::glossary_relations::dsl::*;
+
+    let inverse = kind.inverse().unwrap_or(kind);
+    let relation_ = source_id
+        .eq(_source_id)
+        .and(target_id.eq(_target_id))
+        .and(relation.eq(kind.as_str()));
+    let inverse_ = source_id
+        .eq(_target_id)
+        .and(target_id.eq(_source_id))
+        .and(relation.eq(inverse.as_str()));
+
+    diesel::delete(glossary_relations.filter(relation_.or(inverse_))).execute(conn)
+}
+
+/// Move the relations of a glossary merged into `to`. A relation of `to` with itself
+/// is dropped, one making a hierarchy cycle refuses the merge. The replaced-by
+/// relation follows the status of the merged glossary, it stays.
+pub fn move_relations(conn: &mut PgConnection, from: Uuid, to: Uuid) -> Result<(), ApiError> {
+    use crate::schema::glossary_relations::dsl::*;
+
+    let replaced_by = RelationKind::ReplacedBy.as_str();
+    let moved = glossary_relations
+        .filter(source_id.eq(from))
+        .filter(relation.ne(replaced_by))
+        .select((target_id, relation))
+        .load::<(Uuid, String)>(conn)?;
+
+    diesel::delete(
+        glossary_relations
+            .filter(source_id.eq(from).or(target_id.eq(from)))
+            .filter(relation.ne(replaced_by)),
+    )
+    .execute(conn)?;
+
+    for (other, kind) in moved {
+        if other != to {
+            insert_relation(conn, to, RelationKind::from_str(&kind)?, other)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Parse `/glossary/{id}/relations/{relation}/{target_id}`
+fn parse_relation_path(
+    path: (String, String, String),
+) -> Result<(Uuid, RelationKind, Uuid), ApiError> {
+    let (id, relation, target) = path;
+    let source_id =
+        Uuid::from_str(&id).map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+    let target_id = Uuid::from_str(&target)
+        .map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+
+    let kind = RelationKind::from_str(&relation)?;
+    if kind == RelationKind::ReplacedBy {
+        return Err(ApiError::invalid_input(
+            "replaced-by follows the replaced_by of a deprecated or banned glossary",
+        ));
+    }
+
+    Ok((source_id, kind, target_id))
+}
+
+/// List the relations of a glossary `/glossary/{id}/relations`
+#[get("/glossary/{glossary_id}/relations")]
+pub async fn list(
+    id: web::Path<String>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+
+    let glossary_id =
+        Uuid::from_str(&id).map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+
+    let relations = web::block(move || list_relations(&mut conn, glossary_id)).await??;
+    Ok(web::Json(relations))
+}
+
+/// Relate a glossary to another one `/glossary/{id}/relations/broader/{target_id}`.
+/// Returns the relations of the glossary.
+#[post("/glossary/{glossary_id}/relations/{relation}/{target_id}")]
+pub async fn add(
+    path: web::Path<(String, String, String)>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let (source_id, kind, target_id) = parse_relation_path(path.into_inner())?;
+
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let relations =
+        web::block(move || add_relation(&mut conn, source_id, kind, target_id)).await??;
+    Ok(web::Json(relations))
+}
+
+/// Remove a relation `/glossary/{id}/relations/{relation}/{target_id}`
+#[delete("/glossary/{glossary_id}/relations/{relation}/{target_id}")]
+pub async fn remove(
+    path: web::Path<(String, String, String)>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let (source_id, kind, target_id) = parse_relation_path(path.into_inner())?;
+
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    web::block(move || remove_relation(&mut conn, source_id, kind, target_id)).await??;
+    Ok(web::Json(Message::new("ok")))
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{insert_glossary, service_should_ok_and_return_json, TestContext};
+    use crate::v1::glossary::{delete as delete_glossary, get, Glossary};
+    use crate::v1::trash::restore as restore_glossary;
+    use actix_web::{http, test, App};
+
+    // A broader relation adds the narrower one back,
+    // and the glossary returns its relations with the terms resolved
+    #[actix_rt::test]
+    async fn add_symmetric_relations() {
+        let ctx = TestContext::new("add_symmetric_relations");
+        let pool = ctx.get_pool();
+        let conn = &mut pool.get().expect("could not get db connection from pool");
+
+        let container = insert_glossary(conn, "Container", "test_definition");
+        let docker = insert_glossary(conn, "Docker", "test_definition");
+        let podman = insert_glossary(conn, "Podman", "test_definition");
+        let legacy = insert_glossary(conn, "Legacy runtime", "test_definition");
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(list)
+                .service(add)
+                .service(remove)
+                .service(get),
+        )
+        .await;
+
+        for (source, relation, target) in [
+            (docker, "broader", container),
+            (docker, "related", podman),
+        ] {
+            let req = test::TestRequest::post()
+                .uri(&format!("/glossary/{}/relations/{}/{}", source, relation, target));
+            let _ = service_should_ok_and_return_json!(app, req);
+        }
+
+        let req = test::TestRequest::get().uri(&format!("/glossary/{}", docker));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let glossary: Glossary = test::read_body_json(resp).await;
+        assert_eq!(
+            glossary.relations,
+            vec![
+                Relation {
+                    relation: RelationKind::Broader,
+                    id: container.to_string(),
+                    term: "Container".to_string(),
+                },
+                Relation {
+                    relation: RelationKind::Related,
+                    id: podman.to_string(),
+                    term: "Podman".to_string(),
+                },
+            ]
+        );
+
+        let req = test::TestRequest::get().uri(&format!("/glossary/{}/relations", container));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let relations: Vec<Relation> = test::read_body_json(resp).await;
+        assert_eq!(relations.len(), 1);
+        assert_eq!(relations[0].relation, RelationKind::Narrower);
+        assert_eq!(relations[0].term, "Docker");
+
+        // Removing the narrower relation removes the broader one too
+        let req = test::TestRequest::delete()
+            .uri(&format!("/glossary/{}/relations/narrower/{}", container, docker));
+        let _ = service_should_ok_and_return_json!(app, req);
+
+        let req = test::TestRequest::get().uri(&format!("/glossary/{}/relations", docker));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let relations: Vec<Relation> = test::read_body_json(resp).await;
+        assert_eq!(relations.len(), 1);
+        assert_eq!(relations[0].relation, RelationKind::Related);
+
+        // Relating to itself, with an unknown relation or to an unknown glossary
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/relations/related/{}", docker, docker))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/relations/cousin/{}", docker, podman))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+
+        // replaced-by is set with the status of the glossary
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/relations/replaced-by/{}", legacy, docker))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/relations/related/{}", docker, Uuid::new_v4()))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    // Broader and narrower relations can not make a cycle
+    #[actix_rt::test]
+    async fn reject_hierarchy_cycles() {
+        let ctx = TestContext::new("reject_hierarchy_cycles");
+        let pool = ctx.get_pool();
+        let conn = &mut pool.get().expect("could not get db connection from pool");
+
+        let infra = insert_glossary(conn, "Infrastructure", "test_definition");
+        let container = insert_glossary(conn, "Container", "test_definition");
+        let docker = insert_glossary(conn, "Docker", "test_definition");
+
+        add_relation(conn, docker, RelationKind::Broader, container).unwrap();
+        add_relation(conn, infra, RelationKind::Narrower, container).unwrap();
+
+        // Infrastructure > Container > Docker, so Docker can not be broader than Infrastructure
+        for (source, kind, target) in [
+            (infra, RelationKind::Broader, docker),
+            (docker, RelationKind::Narrower, infra),
+            (container, RelationKind::Broader, docker),
+        ] {
+            let added = add_relation(conn, source, kind, target);
+            assert!(matches!(added, Err(ApiError::Conflict(..))));
+        }
+
+        // Unrelated relations are fine
+        add_relation(conn, infra, RelationKind::Related, docker).unwrap();
+
+        // A glossary in the trash no longer links the hierarchy
+        diesel::update(glossary::table.find(container))
+            .set(glossary::deleted_at.eq(Some(chrono::Utc::now().naive_utc())))
+            .execute(conn)
+            .unwrap();
+        add_relation(conn, infra, RelationKind::Broader, docker).unwrap();
+    }
+
+    // Deleting a glossary hides the relations to it,
+    // restoring it from the trash brings them back
+    #[actix_rt::test]
+    async fn delete_and_restore_glossary_keeps_relations() {
+        let ctx = TestContext::new("delete_and_restore_glossary_keeps_relations");
+        let pool = ctx.get_pool();
+        let conn = &mut pool.get().expect("could not get db connection from pool");
+
+        let container = insert_glossary(conn, "Container", "test_definition");
+        let docker = insert_glossary(conn, "Docker", "test_definition");
+        add_relation(conn, docker, RelationKind::Broader, container).unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(delete_glossary)
+                .service(restore_glossary),
+        )
+        .await;
+
+        let req = test::TestRequest::delete().uri(&format!("/glossary/{}", docker));
+        let _ = service_should_ok_and_return_json!(app, req);
+
+        assert!(list_relations(conn, container).unwrap().is_empty());
+        let count = glossary_relations::table
+            .count()
+            .get_result::<i64>(conn)
+            .unwrap();
+        assert_eq!(count, 2);
+
+        let req = test::TestRequest::post().uri(&format!("/glossary/{}/restore", docker));
+        let _ = service_should_ok_and_return_json!(app, req);
+
+        let relations = list_relations(conn, container).unwrap();
+        assert_eq!(relations.len(), 1);
+        assert_eq!(relations[0].relation, RelationKind::Narrower);
+        assert_eq!(relations[0].id, docker.to_string());
+    }
+}
@@ -0,0 +1,501 @@
+use actix_web::{delete, get, post, put, web, HttpRequest, Responder};
+use actix_web_validator::Json;
+use diesel::{
+    pg::PgConnection, result::Error, Connection, ExpressionMethods, Insertable, QueryDsl,
+    Queryable, RunQueryDsl,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::{collections::HashMap, str::FromStr};
+use uuid::Uuid;
+use validator::Validate;
+
+use super::glossary::{to_glossaries, Glossaries, Glossary, GlossaryDB};
+use crate::{
+    response::{ApiError, ListResp, Message},
+    schema::*,
+    DBPool,
+};
+
+pub type Tags = ListResp<Tag>;
+
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct Tag {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Queryable, Insertable)]
+#[diesel(table_name = tags)]
+pub struct TagDB {
+    pub id: Uuid,
+    pub tag_name: String,
+}
+
+impl TagDB {
+    pub fn to_tag(&self) -> Tag {
+        Tag {
+            id: self.id.to_string(),
+            name: self.tag_name.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Validate)]
+pub struct TagRequest {
+    /// Normalized before it is validated, so a blank name is rejected
+    #[validate(length(min = 1, max = 255))]
+    #[serde(deserialize_with = "normalized_tag_name")]
+    pub name: String,
+}
+
+fn normalized_tag_name<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let name: String = Deserialize::deserialize(deserializer)?;
+    Ok(normalize_tag_name(&name))
+}
+
+/// Tags are matched by name, so "Infra", " infra" and "infra" are the same tag.
+pub fn normalize_tag_name(name: &str) -> String {
+    ammonia::clean(name.trim()).to_lowercase()
+}
+
+pub fn list_tags(conn: &mut PgConnection) -> Result<Vec<TagDB>, Error> {
+    use crate::schema::tags::dsl::*;
+
+    tags.order(tag_name.asc()).load::<TagDB>(conn)
+}
+
+pub fn get_tag(conn: &mut PgConnection, _id: Uuid) -> Result<TagDB, Error> {
+    use crate::schema::tags::dsl::*;
+
+    tags.find(_id).first::<TagDB>(conn)
+}
+
+pub fn get_tag_by_name(conn: &mut PgConnection, name: &str) -> Result<TagDB, Error> {
+    use crate::schema::tags::dsl::*;
+
+    tags.filter(tag_name.eq(normalize_tag_name(name)))
+        .first::<TagDB>(conn)
+}
+
+pub fn create_tag(conn: &mut PgConnection, name: &str) -> Result<TagDB, Error> {
+    use crate::schema::tags::dsl::*;
+
+    diesel::insert_into(tags)
+        .values(TagDB {
+            id: Uuid::new_v4(),
+            tag_name: normalize_tag_name(name),
+        })
+        .get_result::<TagDB>(conn)
+}
+
+/// Return the tag with the given name, creating it when it does not exist yet
+pub fn get_or_create_tag(conn: &mut PgConnection, name: &str) -> Result<TagDB, Error> {
+    use crate::schema::tags::dsl::*;
+
+    diesel::insert_into(tags)
+        .values(TagDB {
+            id: Uuid::new_v4(),
+            tag_name: normalize_tag_name(name),
+        })
+        .on_conflict(tag_name)
+        .do_nothing()
+        .execute(conn)?;
+
+    get_tag_by_name(conn, name)
+}
+
+pub fn update_tag(conn: &mut PgConnection, _id: Uuid, name: &str) -> Result<TagDB, Error> {
+    use crate::schema::tags::dsl::*;
+
+    diesel::update(tags.find(_id))
+        .set(tag_name.eq(normalize_tag_name(name)))
+        .get_result::<TagDB>(conn)
+}
+
+pub fn delete_tag(conn: &mut PgConnection, _id: Uuid) -> Result<usize, Error> {
+    use crate::schema::tags::dsl::*;
+
+    // Database CASCADE constraints detach the tag from every glossary
+    diesel::delete(tags.find(_id)).execute(conn)
+}
+
+/// Attach a tag (by name) to a glossary. Attaching the same tag twice is a no-op.
+pub fn add_glossary_tag(
+    conn: &mut PgConnection,
+    _glossary_id: Uuid,
+    name: &str,
+) -> Result<TagDB, Error> {
+    conn.transaction(|conn| {
+        let tag = get_or_create_tag(conn, name)?;
+
+        diesel::insert_into(glossary_tags::table)
+            .values((
+                glossary_tags::glossary_id.eq(_glossary_id),
+                glossary_tags::tag_id.eq(tag.id),
+            ))
+            .on_conflict_do_nothing()
+            .execute(conn)?;
+
+        Ok(tag)
+    })
+}
+
+pub fn remove_glossary_tag(
+    conn: &mut PgConnection,
+    _glossary_id: Uuid,
+    name: &str,
+) -> Result<usize, Error> {
+    let tag = get_tag_by_name(conn, name)?;
+
+    diesel::delete(
+        glossary_tags::table
+            .filter(glossary_tags::glossary_id.eq(_glossary_id))
+            .filter(glossary_tags::tag_id.eq(tag.id)),
+    )
+    .execute(conn)
+}
+
+/// Tag names of one glossary, sorted by name
+pub fn list_glossary_tag_names(
+    conn: &mut PgConnection,
+    _glossary_id: Uuid,
+) -> Result<Vec<String>, Error> {
+    glossary_tags::table
+        .inner_join(tags::table)
+        .filter(glossary_tags::glossary_id.eq(_glossary_id))
+        .select(tags::tag_name)
+        .order(tags::tag_name.asc())
+        .load::<String>(conn)
+}
+
+/// Tag names of many glossaries at once, keyed by glossary id
+pub fn list_tag_names_by_glossary(
+    conn: &mut PgConnection,
+    glossary_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<String>>, Error> {
+    let rows = glossary_tags::table
+        .inner_join(tags::table)
+        .filter(glossary_tags::glossary_id.eq_any(glossary_ids))
+        .select((glossary_tags::glossary_id, tags::tag_name))
+        .order(tags::tag_name.asc())
+        .load::<(Uuid, String)>(conn)?;
+
+    let mut tags_by_glossary: HashMap<Uuid, Vec<String>> = HashMap::new();
+    for (glossary_id, tag_name) in rows {
+        tags_by_glossary
+            .entry(glossary_id)
+            .or_default()
+            .push(tag_name);
+    }
+
+    Ok(tags_by_glossary)
+}
+
+pub fn list_glossary_by_tag(
+    conn: &mut PgConnection,
+    name: &str,
+    me: Option<&str>,
+) -> Result<Vec<Glossary>, Error> {
+    let tag = get_tag_by_name(conn, name)?;
+
+    let glossaries = glossary::table
+        .inner_join(glossary_tags::table)
+        .filter(glossary_tags::tag_id.eq(tag.id))
+        .filter(glossary::deleted_at.is_null())
+        .select(glossary::all_columns)
+        .order(glossary::term.asc())
+        .load::<GlossaryDB>(conn)?;
+
+    to_glossaries(conn, glossaries, me)
+}
+
+/// List all tags
+#[get("/tags")]
+pub async fn list(pool: web::Data<DBPool>) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+
+    let tags = web::block(move || list_tags(&mut conn)).await??;
+    let tags: Vec<Tag> = tags.iter().map(|t| t.to_tag()).collect();
+
+    Ok(web::Json(Tags::from(&tags)))
+}
+
+/// Create a new tag
+#[post("/tags")]
+pub async fn create(
+    Json(value): Json<TagRequest>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+
+    let tag = web::block(move || create_tag(&mut conn, &value.name)).await??;
+    Ok(web::Json(tag.to_tag()))
+}
+
+/// Find a tag by id
+#[get("/tags/{id}")]
+pub async fn get(
+    id: web::Path<String>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+
+    let tag_id =
+        Uuid::from_str(&id).map_err(|_| ApiError::invalid_input("Invalid tag ID format"))?;
+
+    let tag = web::block(move || get_tag(&mut conn, tag_id)).await??;
+    Ok(web::Json(tag.to_tag()))
+}
+
+/// Rename a tag by id
+#[put("/tags/{id}")]
+pub async fn update(
+    id: web::Path<String>,
+    Json(value): Json<TagRequest>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+
+    let tag_id =
+        Uuid::from_str(&id).map_err(|_| ApiError::invalid_input("Invalid tag ID format"))?;
+
+    let tag = web::block(move || update_tag(&mut conn, tag_id, &value.name)).await??;
+    Ok(web::Json(tag.to_tag()))
+}
+
+/// Delete a tag by id, detaching it from every glossary
+#[delete("/tags/{id}")]
+pub async fn delete(
+    id: web::Path<String>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+
+    let tag_id =
+        Uuid::from_str(&id).map_err(|_| ApiError::invalid_input("Invalid tag ID format"))?;
+
+    web::block(move || delete_tag(&mut conn, tag_id)).await??;
+    Ok(web::Json(Message::new("deleted")))
+}
+
+/// List glossaries tagged with `/tags/{name}/glossary`
+#[get("/tags/{name}/glossary")]
+pub async fn list_glossary(
+    name: web::Path<String>,
+    pool: web::Data<DBPool>,
+    req: HttpRequest,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let me = req
+        .headers()
+        .get(crate::AUTHENTICATED_USER_HEADER)
+        .map(|email| email.to_str().unwrap().to_string());
+
+    let glossaries =
+        web::block(move || list_glossary_by_tag(&mut conn, &name, me.as_deref())).await??;
+    Ok(web::Json(Glossaries::from(&glossaries)))
+}
+
+/// Attach a tag to a glossary `/glossary/{id}/tags/{name}`
+#[post("/glossary/{glossary_id}/tags/{name}")]
+pub async fn attach(
+    path: web::Path<(String, String)>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let (id, name) = path.into_inner();
+
+    let glossary_id =
+        Uuid::from_str(&id).map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+    if normalize_tag_name(&name).is_empty() {
+        return Err(ApiError::invalid_input("Tag name cannot be empty"));
+    }
+
+    let tag = web::block(move || add_glossary_tag(&mut conn, glossary_id, &name)).await??;
+    Ok(web::Json(tag.to_tag()))
+}
+
+/// Detach a tag from a glossary `/glossary/{id}/tags/{name}`
+#[delete("/glossary/{glossary_id}/tags/{name}")]
+pub async fn detach(
+    path: web::Path<(String, String)>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let (id, name) = path.into_inner();
+
+    let glossary_id =
+        Uuid::from_str(&id).map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+
+    web::block(move || remove_glossary_tag(&mut conn, glossary_id, &name)).await??;
+    Ok(web::Json(Message::new("ok")))
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{insert_glossary, service_should_ok_and_return_json, TestContext};
+    use crate::v1::glossary;
+    use actix_web::{http, test, App};
+
+    // Create a tag, then the same tag with different case should conflict
+    #[actix_rt::test]
+    async fn create_tag_then_list() {
+        let ctx = TestContext::new("create_tag_then_list");
+        let pool = ctx.get_pool();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(list)
+                .service(create),
+        )
+        .await;
+
+        let req = test::TestRequest::post().uri("/tags").set_json(TagRequest {
+            name: " Infra ".to_string(),
+        });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let tag: Tag = test::read_body_json(resp).await;
+        assert_eq!(tag.name, "infra");
+
+        // Tag names are unique
+        let req = test::TestRequest::post()
+            .uri("/tags")
+            .set_json(TagRequest {
+                name: "INFRA".to_string(),
+            })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::CONFLICT);
+
+        let req = test::TestRequest::get().uri("/tags");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let tags: Tags = test::read_body_json(resp).await;
+        assert_eq!(tags.count, 1);
+    }
+
+    // A name which is blank once normalized is rejected on create and rename
+    #[actix_rt::test]
+    async fn blank_tag_name_is_rejected() {
+        let ctx = TestContext::new("blank_tag_name_is_rejected");
+        let pool = ctx.get_pool();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(create)
+                .service(update),
+        )
+        .await;
+
+        let req = test::TestRequest::post().uri("/tags").set_json(TagRequest {
+            name: "infra".to_string(),
+        });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let tag: Tag = test::read_body_json(resp).await;
+
+        let blank = serde_json::json!({ "name": "   " });
+        let requests = [
+            test::TestRequest::post().uri("/tags"),
+            test::TestRequest::put().uri(&format!("/tags/{}", tag.id)),
+        ];
+        for req in requests {
+            let resp = test::call_service(&app, req.set_json(&blank).to_request()).await;
+            assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+        }
+    }
+
+    // Attach a tag to a glossary, the glossary should be listed by the tag
+    // and the tag should be returned on the glossary.
+    #[actix_rt::test]
+    async fn attach_tag_then_list_glossary() {
+        let ctx = TestContext::new("attach_tag_then_list_glossary");
+        let pool = ctx.get_pool();
+        let conn = &mut pool.get().expect("could not get db connection from pool");
+
+        let tagged_id = insert_glossary(conn, "kubernetes", "definition of kubernetes");
+        let _ = insert_glossary(conn, "excel", "definition of excel");
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(attach)
+                .service(list_glossary)
+                .service(glossary::get),
+        )
+        .await;
+
+        // Attaching twice is a no-op
+        for _ in 0..2 {
+            let req = test::TestRequest::post().uri(&format!("/glossary/{}/tags/infra", tagged_id));
+            let _ = service_should_ok_and_return_json!(app, req);
+        }
+
+        let req = test::TestRequest::get().uri("/tags/infra/glossary");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let glossaries: Glossaries = test::read_body_json(resp).await;
+        assert_eq!(glossaries.count, 1);
+        assert_eq!(glossaries.results[0].term, "kubernetes");
+        assert_eq!(glossaries.results[0].tags, vec!["infra".to_string()]);
+
+        let req = test::TestRequest::get().uri(&format!("/glossary/{}", tagged_id));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let glossary: Glossary = test::read_body_json(resp).await;
+        assert_eq!(glossary.tags, vec!["infra".to_string()]);
+    }
+
+    // Detach a tag from a glossary
+    #[actix_rt::test]
+    async fn attach_then_detach_tag() {
+        let ctx = TestContext::new("attach_then_detach_tag");
+        let pool = ctx.get_pool();
+        let conn = &mut pool.get().expect("could not get db connection from pool");
+
+        let glossary_id = insert_glossary(conn, "kubernetes", "definition of kubernetes");
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(attach)
+                .service(detach)
+                .service(list_glossary),
+        )
+        .await;
+
+        let req = test::TestRequest::post().uri(&format!("/glossary/{}/tags/infra", glossary_id));
+        let _ = service_should_ok_and_return_json!(app, req);
+
+        let req = test::TestRequest::delete().uri(&format!("/glossary/{}/tags/infra", glossary_id));
+        let _ = service_should_ok_and_return_json!(app, req);
+
+        let req = test::TestRequest::get().uri("/tags/infra/glossary");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let glossaries: Glossaries = test::read_body_json(resp).await;
+        assert_eq!(glossaries.count, 0);
+    }
+
+    // Listing glossaries of an unknown tag should return 404 NOT FOUND
+    #[actix_rt::test]
+    async fn list_glossary_unknown_tag() {
+        let ctx = TestContext::new("list_glossary_unknown_tag");
+        let pool = ctx.get_pool();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(list_glossary),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/tags/unknown/glossary")
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,159 @@
+use actix_web::{get, web, Responder};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use diesel::{
+    pg::PgConnection, result::Error, ExpressionMethods, Insertable, QueryDsl, Queryable,
+    RunQueryDsl,
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::glossary::GlossaryDB;
+use crate::{
+    response::{ApiError, ListResp},
+    schema::*,
+    DBPool,
+};
+
+pub type Tombstones = ListResp<Tombstone>;
+
+/// A deleted glossary with its last definition and who deleted it
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct Tombstone {
+    pub glossary_id: String,
+    pub term: String,
+    pub definition: String,
+    pub revision: i32,
+    pub who: Option<String>,
+    pub deleted_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Queryable, Insertable)]
+#[diesel(table_name = glossary_tombstones)]
+pub struct TombstoneDB {
+    pub id: Uuid,
+    pub glossary_id: Uuid,
+    pub term: String,
+    pub definition: String,
+    pub revision: i32,
+    pub who: Option<String>,
+    pub deleted_at: NaiveDateTime,
+}
+
+impl TombstoneDB {
+    pub fn to_tombstone(&self) -> Tombstone {
+        Tombstone {
+            glossary_id: self.glossary_id.to_string(),
+            term: self.term.clone(),
+            definition: self.definition.clone(),
+            revision: self.revision,
+            who: self.who.clone(),
+            deleted_at: Utc.from_utc_datetime(&self.deleted_at),
+        }
+    }
+}
+
+pub fn create_tombstone(
+    conn: &mut PgConnection,
+    deleted: &GlossaryDB,
+    who: Option<String>,
+) -> Result<usize, Error> {
+    diesel::insert_into(glossary_tombstones::table)
+        .values(TombstoneDB {
+            id: Uuid::new_v4(),
+            glossary_id: deleted.id,
+            term: deleted.term.clone(),
+            definition: deleted.definition.clone(),
+            revision: deleted.revision,
+            who,
+            deleted_at: Utc::now().naive_utc(),
+        })
+        .execute(conn)
+}
+
+pub fn list_tombstones(conn: &mut PgConnection, limit: i64) -> Result<Vec<TombstoneDB>, Error> {
+    use crate::schema::glossary_tombstones::dsl::*;
+
+    glossary_tombstones
+        .order(deleted_at.desc())
+        .limit(limit)
+        .load::<TombstoneDB>(conn)
+}
+
+pub fn count_tombstones(conn: &mut PgConnection) -> Result<i64, Error> {
+    glossary_tombstones::table.count().get_result(conn)
+}
+
+#[derive(Deserialize)]
+pub struct TombstoneQuery {
+    pub limit: Option<u8>,
+}
+
+/// List recently deleted glossaries `/admin/glossary-deleted?limit=20`
+#[get("/admin/glossary-deleted")]
+pub async fn list(
+    query: web::Query<TombstoneQuery>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let limit = query.limit.unwrap_or(20) as i64;
+
+    let (tombstones, total) = web::block(move || {
+        let tombstones = list_tombstones(&mut conn, limit)?;
+        let total = count_tombstones(&mut conn)?;
+        Ok::<_, Error>((tombstones, total))
+    })
+    .await??;
+    let tombstones: Vec<Tombstone> = tombstones.iter().map(|t| t.to_tombstone()).collect();
+
+    Ok(web::Json(Tombstones::from(&tombstones).with_total(total)))
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{service_should_ok_and_return_json, TestContext};
+    use crate::v1::glossary::{self, Glossary, GlossaryRequest};
+    use actix_web::{test, App};
+
+    // Create then delete a glossary. The deletion should be listed
+    // with the last definition and who deleted it.
+    #[actix_rt::test]
+    async fn list_deleted_glossary() {
+        let ctx = TestContext::new("list_deleted_glossary");
+        let pool = ctx.get_pool();
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(glossary::create)
+                .service(glossary::delete)
+                .service(list),
+        )
+        .await;
+
+        let req = test::TestRequest::post().uri("/glossary").set_json(GlossaryRequest {
+            term: Some("test_term".to_string()),
+            definition: Some("test_definition".to_string()),
+            ..Default::default()
+        });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let created: Glossary = test::read_body_json(resp).await;
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/glossary/{}", created.id))
+            .insert_header((crate::AUTHENTICATED_USER_HEADER, "admin@example.com"));
+        let _ = service_should_ok_and_return_json!(app, req);
+
+        let req = test::TestRequest::get().uri("/admin/glossary-deleted");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let tombstones: Tombstones = test::read_body_json(resp).await;
+        assert_eq!(tombstones.count, 1);
+        assert_eq!(tombstones.results[0].glossary_id, created.id);
+        assert_eq!(tombstones.results[0].definition, "test_definition");
+        assert_eq!(
+            tombstones.results[0].who,
+            Some("admin@example.com".to_string())
+        );
+    }
+}
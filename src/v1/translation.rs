@@ -0,0 +1,613 @@
+use actix_web::{
+    delete, get,
+    http::header::{AcceptLanguage, Header, Preference},
+    put, web, HttpRequest, Responder,
+};
+use actix_web_validator::Json;
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use diesel::{
+    pg::PgConnection, result::Error, upsert::excluded, ExpressionMethods, Insertable, QueryDsl,
+    Queryable, RunQueryDsl,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, env, str::FromStr};
+use uuid::Uuid;
+use validator::Validate;
+
+use super::glossary::{cleanup_string, get_glossary, Glossary};
+use crate::{
+    response::{ApiError, Message},
+    schema::*,
+    DBPool,
+};
+
+/// The language glossaries are written in, set `SOURCE_LANGUAGE` to change it.
+pub const DEFAULT_SOURCE_LANGUAGE: &str = "en";
+
+pub fn source_language() -> String {
+    env::var("SOURCE_LANGUAGE")
+        .ok()
+        .and_then(|lang| normalize_locale(&lang).ok())
+        .unwrap_or_else(|| DEFAULT_SOURCE_LANGUAGE.to_string())
+}
+
+/// A translation of the term and definition of a glossary
+#[derive(Debug, Clone, Deserialize, Serialize, Eq, PartialEq)]
+pub struct Translation {
+    pub locale: String,
+    pub term: String,
+    pub definition: String,
+    /// The revision of the glossary this translates
+    pub source_revision: i32,
+    /// Set when the source definition changed since the translation
+    pub outdated: bool,
+    pub who: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Queryable, Insertable)]
+#[diesel(table_name = glossary_translations)]
+pub struct TranslationDB {
+    pub glossary_id: Uuid,
+    pub locale: String,
+    pub term: String,
+    pub definition: String,
+    pub source_revision: i32,
+    pub outdated: bool,
+    pub who: Option<String>,
+    pub updated_at: NaiveDateTime,
+}
+
+impl TranslationDB {
+    pub fn to_translation(&self) -> Translation {
+        Translation {
+            locale: self.locale.clone(),
+            term: self.term.clone(),
+            definition: self.definition.clone(),
+            source_revision: self.source_revision,
+            outdated: self.outdated,
+            who: self.who.clone(),
+            updated_at: Utc.from_utc_datetime(&self.updated_at),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Validate)]
+pub struct TranslationRequest {
+    #[validate(required, length(min = 1, max = 255))]
+    #[serde(deserialize_with = "cleanup_string")]
+    pub term: Option<String>,
+    #[validate(required, length(min = 1))]
+    #[serde(deserialize_with = "cleanup_string")]
+    pub definition: Option<String>,
+}
+
+/// `?lang=` to ask for a language, over the `Accept-Language` header
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct LangQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<String>,
+}
+
+/// Check a BCP-47 language tag and write it in its usual case: "ja-JP", "zh-Hant-TW"
+pub fn normalize_locale(locale: &str) -> Result<String, ApiError> {
+    let invalid = || ApiError::invalid_input(&format!("Invalid locale: {}", locale));
+
+    let subtags: Vec<&str> = locale.trim().split(['-', '_']).collect();
+    let language = subtags[0];
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err(invalid());
+    }
+
+    let mut normalized = vec![language.to_ascii_lowercase()];
+    for subtag in &subtags[1..] {
+        if !(1..=8).contains(&subtag.len()) || !subtag.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(invalid());
+        }
+
+        normalized.push(match subtag.len() {
+            // Script
+            4 if subtag.chars().all(|c| c.is_ascii_alphabetic()) => {
+                subtag[..1].to_ascii_uppercase() + &subtag[1..].to_ascii_lowercase()
+            }
+            // Region
+            2 => subtag.to_ascii_uppercase(),
+            _ => subtag.to_ascii_lowercase(),
+        });
+    }
+
+    Ok(normalized.join("-"))
+}
+
+/// The languages asked for, most preferred first: `?lang=` if given,
+/// else the `Accept-Language` header. `*` stands for any language.
+pub fn preferred_locales(lang: Option<&str>, req: &HttpRequest) -> Result<Vec<String>, ApiError> {
+    if let Some(lang) = lang {
+        return Ok(vec![normalize_locale(lang)?]);
+    }
+
+    let ranked = AcceptLanguage::parse(req)
+        .map(|accept| accept.ranked())
+        .unwrap_or_default();
+
+    Ok(ranked
+        .into_iter()
+        .filter_map(|preference| match preference {
+            Preference::Any => Some("*".to_string()),
+            Preference::Specific(tag) => normalize_locale(tag.as_str()).ok(),
+        })
+        .collect())
+}
+
+/// Whether a language asked for is served by a locale: "ja" by "ja-JP" and the reverse
+fn locale_matches(preferred: &str, locale: &str) -> bool {
+    let (preferred, locale) = (preferred.to_ascii_lowercase(), locale.to_ascii_lowercase());
+
+    preferred == locale
+        || locale.starts_with(&format!("{}-", preferred))
+        || preferred.starts_with(&format!("{}-", locale))
+}
+
+/// Pick the translation to serve: the first language asked for which is either
+/// translated or the source language. `None` serves the source language.
+pub fn negotiate<'a>(
+    preferred: &[String],
+    source: &str,
+    translations: &'a [TranslationDB],
+) -> Option<&'a TranslationDB> {
+    for lang in preferred {
+        if lang == "*" || locale_matches(lang, source) {
+            return None;
+        }
+
+        // An exact match wins over a match on the language only
+        let found = translations
+            .iter()
+            .find(|t| t.locale.eq_ignore_ascii_case(lang))
+            .or_else(|| translations.iter().find(|t| locale_matches(lang, &t.locale)));
+        if found.is_some() {
+            return found;
+        }
+    }
+
+    None
+}
+
+/// Serve glossaries in the languages asked for, falling back to the source language.
+/// Translated glossaries carry the `locale` of their term and definition.
+pub fn translate_glossaries(
+    conn: &mut PgConnection,
+    glossaries: Vec<Glossary>,
+    preferred: &[String],
+) -> Result<Vec<Glossary>, Error> {
+    if preferred.is_empty() || glossaries.is_empty() {
+        return Ok(glossaries);
+    }
+
+    let ids: Vec<Uuid> = glossaries
+        .iter()
+        .filter_map(|g| Uuid::from_str(&g.id).ok())
+        .collect();
+    let mut translations_by_glossary = list_translations_by_glossary(conn, &ids)?;
+    let source = source_language();
+
+    Ok(glossaries
+        .into_iter()
+        .map(|g| {
+            let translations = Uuid::from_str(&g.id)
+                .ok()
+                .and_then(|id| translations_by_glossary.remove(&id))
+                .unwrap_or_default();
+
+            match negotiate(preferred, &source, &translations) {
+                Some(translation) => Glossary {
+                    term: translation.term.clone(),
+                    definition: translation.definition.clone(),
+                    locale: Some(translation.locale.clone()),
+                    translation_outdated: translation.outdated,
+                    ..g
+                },
+                None => g,
+            }
+        })
+        .collect())
+}
+
+/// Translations of one glossary, by locale
+pub fn list_translations(
+    conn: &mut PgConnection,
+    _glossary_id: Uuid,
+) -> Result<Vec<TranslationDB>, Error> {
+    use crate::schema::glossary_translations::dsl::*;
+
+    glossary_translations
+        .filter(glossary_id.eq(_glossary_id))
+        .order(locale.asc())
+        .load::<TranslationDB>(conn)
+}
+
+/// Translations of many glossaries at once, keyed by glossary id
+pub fn list_translations_by_glossary(
+    conn: &mut PgConnection,
+    glossary_ids: &[Uuid],
+) -> Result<HashMap<Uuid, Vec<TranslationDB>>, Error> {
+    use crate::schema::glossary_translations::dsl::*;
+
+    let rows = glossary_translations
+        .filter(glossary_id.eq_any(glossary_ids))
+        .order(locale.asc())
+        .load::<TranslationDB>(conn)?;
+
+    let mut translations_by_glossary: HashMap<Uuid, Vec<TranslationDB>> = HashMap::new();
+    for row in rows {
+        translations_by_glossary
+            .entry(row.glossary_id)
+            .or_default()
+            .push(row);
+    }
+
+    Ok(translations_by_glossary)
+}
+
+/// Add or update the translation of a glossary in a locale. The translation is
+/// up to date with the current revision of the glossary.
+pub fn save_translation(
+    conn: &mut PgConnection,
+    _glossary_id: Uuid,
+    _locale: &str,
+    value: TranslationRequest,
+    _who: Option<String>,
+) -> Result<TranslationDB, Error> {
+    use crate::schema::glossary_translations::dsl::*;
+
+    let source = get_glossary(conn, _glossary_id)?;
+
+    diesel::insert_into(glossary_translations)
+        .values(TranslationDB {
+            glossary_id: _glossary_id,
+            locale: _locale.to_string(),
+            term: value.term.unwrap_or_default(),
+            definition: value.definition.unwrap_or_default(),
+            source_revision: source.revision,
+            outdated: false,
+            who: _who,
+            updated_at: Utc::now().naive_utc(),
+        })
+        .on_conflict((glossary_id, locale))
+        .do_update()
+        .set((
+            term.eq(excluded(term)),
+            definition.eq(excluded(definition)),
+            source_revision.eq(excluded(source_revision)),
+            outdated.eq(false),
+            who.eq(excluded(who)),
+            updated_at.eq(excluded(updated_at)),
+        ))
+        .get_result::<TranslationDB>(conn)
+}
+
+pub fn delete_translation(
+    conn: &mut PgConnection,
+    _glossary_id: Uuid,
+    _locale: &str,
+) -> Result<usize, Error> {
+    use crate::schema::glossary_translations::dsl::*;
+
+    diesel::delete(
+        glossary_translations
+            .filter(glossary_id.eq(_glossary_id))
+            .filter(locale.eq(_locale)),
+    )
+    .execute(conn)
+}
+
+/// Move the translations of a glossary merged into `to`, they are outdated as they
+/// translate the merged definition. When both glossaries are translated to the same
+/// locale, the merge is a conflict until one of the translations is removed.
+pub fn move_translations(conn: &mut PgConnection, from: Uuid, to: Uuid) -> Result<usize, ApiError> {
+    use crate::schema::glossary_translations::dsl::*;
+
+    let translated = glossary_translations
+        .filter(glossary_id.eq(to))
+        .select(locale)
+        .load::<String>(conn)?;
+    let conflicts = glossary_translations
+        .filter(glossary_id.eq(from))
+        .filter(locale.eq_any(&translated))
+        .select(locale)
+        .order(locale.asc())
+        .load::<String>(conn)?;
+    if !conflicts.is_empty() {
+        return Err(ApiError::conflict(&format!(
+            "Both glossaries are translated to {}, remove one of the translations first",
+            conflicts.join(", ")
+        )));
+    }
+
+    Ok(
+        diesel::update(glossary_translations.filter(glossary_id.eq(from)))
+            .set((glossary_id.eq(to), outdated.eq(true)))
+            .execute(conn)?,
+    )
+}
+
+/// Flag the translations of a glossary as outdated, once its definition changed
+pub fn mark_translations_outdated(
+    conn: &mut PgConnection,
+    _glossary_id: Uuid,
+) -> Result<usize, Error> {
+    use crate::schema::glossary_translations::dsl::*;
+
+    diesel::update(glossary_translations.filter(glossary_id.eq(_glossary_id)))
+        .set(outdated.eq(true))
+        .execute(conn)
+}
+
+/// List the translations of a glossary `/glossary/{id}/translations`
+#[get("/glossary/{glossary_id}/translations")]
+pub async fn list(
+    id: web::Path<String>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+
+    let glossary_id =
+        Uuid::from_str(&id).map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+
+    let translations = web::block(move || list_translations(&mut conn, glossary_id)).await??;
+    Ok(web::Json(
+        translations
+            .iter()
+            .map(|t| t.to_translation())
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// Add or update a translation `/glossary/{id}/translations/{locale}`
+#[put("/glossary/{glossary_id}/translations/{locale}")]
+pub async fn save(
+    path: web::Path<(String, String)>,
+    json: Json<TranslationRequest>,
+    req: HttpRequest,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let (id, locale) = path.into_inner();
+
+    let glossary_id =
+        Uuid::from_str(&id).map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+    let locale = normalize_locale(&locale)?;
+    if locale_matches(&locale, &source_language()) {
+        return Err(ApiError::invalid_input(
+            "The source language is edited on the glossary, not as a translation",
+        ));
+    }
+    let who = req
+        .headers()
+        .get(crate::AUTHENTICATED_USER_HEADER)
+        .map(|email| email.to_str().unwrap().to_string());
+
+    let saved = web::block(move || {
+        save_translation(&mut conn, glossary_id, &locale, json.into_inner(), who)
+    })
+    .await??;
+    Ok(web::Json(saved.to_translation()))
+}
+
+/// Remove a translation `/glossary/{id}/translations/{locale}`
+#[delete("/glossary/{glossary_id}/translations/{locale}")]
+pub async fn remove(
+    path: web::Path<(String, String)>,
+    pool: web::Data<DBPool>,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let (id, locale) = path.into_inner();
+
+    let glossary_id =
+        Uuid::from_str(&id).map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+    let locale = normalize_locale(&locale)?;
+
+    web::block(move || delete_translation(&mut conn, glossary_id, &locale)).await??;
+    Ok(web::Json(Message::new("ok")))
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{insert_glossary, service_should_ok_and_return_json, TestContext};
+    use crate::v1::glossary::{get, search, update, Glossaries, GlossaryRequest, ListQuery};
+    use actix_web::{http, test, App};
+
+    fn translate(
+        glossary_id: Uuid,
+        locale: &str,
+        term: &str,
+        definition: &str,
+    ) -> test::TestRequest {
+        test::TestRequest::put()
+            .uri(&format!("/glossary/{}/translations/{}", glossary_id, locale))
+            .set_json(&TranslationRequest {
+                term: Some(term.to_string()),
+                definition: Some(definition.to_string()),
+            })
+    }
+
+    // Translations are served by `?lang=` or `Accept-Language`, else the
+    // source language. A language asked for before a translated one wins.
+    #[actix_rt::test]
+    async fn negotiate_translations() {
+        let ctx = TestContext::new("negotiate_translations");
+        let pool = ctx.get_pool();
+        let conn = &mut pool.get().expect("could not get db connection from pool");
+
+        let cache = insert_glossary(conn, "Cache", "A store of copies for fast access");
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(list)
+                .service(save)
+                .service(remove)
+                .service(get)
+                .service(search)
+                .service(update)
+                .service(crate::v1::glossary::list),
+        )
+        .await;
+
+        for (locale, term, definition) in [
+            ("vi", "Bộ nhớ đệm", "Nơi lưu bản sao để truy cập nhanh"),
+            ("ja_jp", "キャッシュ", "高速アクセスのためのコピーの保存場所"),
+        ] {
+            let req = translate(cache, locale, term, definition);
+            let _ = service_should_ok_and_return_json!(app, req);
+        }
+
+        // The source language can not be served as a translation
+        for locale in ["en", "en-GB"] {
+            let req = translate(cache, locale, "Cache", "A store").to_request();
+            let resp = test::call_service(&app, req).await;
+            assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+        }
+
+        let req = test::TestRequest::get().uri(&format!("/glossary/{}/translations", cache));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let translations: Vec<Translation> = test::read_body_json(resp).await;
+        let locales: Vec<&str> = translations.iter().map(|t| t.locale.as_str()).collect();
+        assert_eq!(locales, vec!["ja-JP", "vi"]);
+
+        let cases = [
+            (Some("vi"), None, Some("vi")),
+            (None, Some("ja"), Some("ja-JP")),
+            (None, Some("fr, vi;q=0.8, en;q=0.9"), None),
+            (None, Some("fr, vi-VN;q=0.5"), Some("vi")),
+            (None, Some("fr"), None),
+            (None, Some("*"), None),
+            (None, None, None),
+        ];
+        for (lang, accept_language, expected) in cases {
+            let query = serde_urlencoded::to_string(LangQuery {
+                lang: lang.map(str::to_string),
+            })
+            .unwrap();
+            let mut req = test::TestRequest::get().uri(&format!("/glossary/{}?{}", cache, query));
+            if let Some(accept_language) = accept_language {
+                req = req.insert_header((http::header::ACCEPT_LANGUAGE, accept_language));
+            }
+            let resp = service_should_ok_and_return_json!(app, req);
+            let content_language = resp.headers().get(http::header::CONTENT_LANGUAGE).cloned();
+            let etag = resp.headers().get(http::header::ETAG).cloned().unwrap();
+            assert_eq!(resp.headers().get(http::header::VARY).unwrap(), "Accept-Language");
+            let glossary: Glossary = test::read_body_json(resp).await;
+
+            assert_eq!(glossary.locale.as_deref(), expected, "{:?}", accept_language);
+            assert_eq!(content_language.is_some(), expected.is_some());
+            // Each language has its own ETag, only the source one is strong
+            match expected {
+                Some(locale) => assert_eq!(etag, format!("W/\"0-{}\"", locale).as_str()),
+                None => assert_eq!(etag, "\"0\""),
+            }
+            if expected.is_none() {
+                assert_eq!(glossary.term, "Cache");
+            }
+        }
+
+        // Lists and search results are translated too
+        let query = serde_urlencoded::to_string(ListQuery {
+            lang: Some("vi".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+        let req = test::TestRequest::get().uri(&format!("/glossary?{}", query));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let page: Glossaries = test::read_body_json(resp).await;
+        assert_eq!(page.results[0].term, "Bộ nhớ đệm");
+
+        let req = test::TestRequest::get()
+            .uri("/glossary-search?q=cache")
+            .insert_header((http::header::ACCEPT_LANGUAGE, "ja-JP"));
+        let resp = service_should_ok_and_return_json!(app, req);
+        assert_eq!(resp.headers().get(http::header::VARY).unwrap(), "Accept-Language");
+        let page: Glossaries = test::read_body_json(resp).await;
+        assert_eq!(page.results[0].term, "キャッシュ");
+
+        // The ETag of a translation can not be used to edit the glossary
+        let req = test::TestRequest::put()
+            .uri(&format!("/glossary/{}", cache))
+            .insert_header((http::header::IF_MATCH, "W/\"0-vi\""))
+            .set_json(&GlossaryRequest {
+                term: Some("Bộ nhớ đệm".to_string()),
+                definition: Some("Nơi lưu bản sao để truy cập nhanh".to_string()),
+                ..Default::default()
+            })
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+
+        // Not a language tag
+        let req = test::TestRequest::get()
+            .uri(&format!("/glossary/{}?lang=not-a-locale!", cache))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::delete().uri(&format!("/glossary/{}/translations/VI", cache));
+        let _ = service_should_ok_and_return_json!(app, req);
+        let req = test::TestRequest::get().uri(&format!("/glossary/{}?lang=vi", cache));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let glossary: Glossary = test::read_body_json(resp).await;
+        assert_eq!(glossary.locale, None);
+    }
+
+    // Changing the source definition flags the translations as outdated,
+    // until they are saved again
+    #[actix_rt::test]
+    async fn translations_become_outdated() {
+        let ctx = TestContext::new("translations_become_outdated");
+        let pool = ctx.get_pool();
+        let conn = &mut pool.get().expect("could not get db connection from pool");
+
+        let cache = insert_glossary(conn, "Cache", "A store of copies");
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(list)
+                .service(save)
+                .service(get)
+                .service(update),
+        )
+        .await;
+
+        let req = translate(cache, "vi", "Bộ nhớ đệm", "Nơi lưu bản sao");
+        let _ = service_should_ok_and_return_json!(app, req);
+
+        // Only the term changes, the translation is still up to date
+        let edits = [
+            ("Caching", "A store of copies", false),
+            ("Caching", "A store of copies for fast access", true),
+        ];
+        for (term, definition, outdated) in edits {
+            let req = test::TestRequest::put()
+                .uri(&format!("/glossary/{}", cache))
+                .set_json(&GlossaryRequest {
+                    term: Some(term.to_string()),
+                    definition: Some(definition.to_string()),
+                    ..Default::default()
+                });
+            let _ = service_should_ok_and_return_json!(app, req);
+
+            let req = test::TestRequest::get().uri(&format!("/glossary/{}?lang=vi", cache));
+            let resp = service_should_ok_and_return_json!(app, req);
+            let glossary: Glossary = test::read_body_json(resp).await;
+            assert_eq!(glossary.translation_outdated, outdated);
+        }
+
+        let req = translate(cache, "vi", "Bộ nhớ đệm", "Nơi lưu bản sao để truy cập nhanh");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let translation: Translation = test::read_body_json(resp).await;
+        assert!(!translation.outdated);
+        assert_eq!(translation.source_revision, 2);
+    }
+}
@@ -0,0 +1,239 @@
+use actix_web::{delete, get, post, web, HttpRequest, Responder};
+use chrono::{Duration, Utc};
+use diesel::{
+    pg::PgConnection, result::Error, Connection, ExpressionMethods, OptionalExtension, QueryDsl,
+    RunQueryDsl,
+};
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::{env, str::FromStr};
+use uuid::Uuid;
+
+use super::{
+    glossary::{Glossaries, Glossary, GlossaryDB},
+    glossary_history::{create_glossary_history, HistoryAction},
+    tag::list_glossary_tag_names,
+};
+use crate::{response::ApiError, DBPool};
+
+/// Glossaries stay in the trash for this many days before being purged,
+/// set `TRASH_RETENTION_DAYS` to change it.
+pub const DEFAULT_TRASH_RETENTION_DAYS: i64 = 30;
+
+pub fn trash_retention_days() -> i64 {
+    env::var("TRASH_RETENTION_DAYS")
+        .ok()
+        .and_then(|days| days.parse::<i64>().ok())
+        .unwrap_or(DEFAULT_TRASH_RETENTION_DAYS)
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct PurgeResp {
+    pub purged: usize,
+    pub retention_days: i64,
+}
+
+pub fn list_trash(conn: &mut PgConnection) -> Result<Vec<GlossaryDB>, Error> {
+    use crate::schema::glossary::dsl::*;
+
+    glossary
+        .filter(deleted_at.is_not_null())
+        .order(deleted_at.desc())
+        .load::<GlossaryDB>(conn)
+}
+
+/// Move a glossary out of the trash, as a new revision
+pub fn restore_glossary(
+    conn: &mut PgConnection,
+    _id: Uuid,
+    who: Option<String>,
+) -> Result<GlossaryDB, Error> {
+    use crate::schema::glossary::dsl::*;
+
+    conn.transaction(|conn| {
+        let restored = diesel::update(glossary.find(_id).filter(deleted_at.is_not_null()))
+            .set((
+                deleted_at.eq(None::<chrono::NaiveDateTime>),
+                revision.eq(revision + 1),
+                updated_at.eq(Utc::now().naive_utc()),
+            ))
+            .get_result::<GlossaryDB>(conn)
+            .optional()?
+            .ok_or(Error::NotFound)?;
+
+        create_glossary_history(conn, &restored, who, HistoryAction::Restore, None, None)?;
+
+        Ok(restored)
+    })
+}
+
+/// Hard delete the glossaries which are in the trash for more than `retention_days`.
+/// Their likes and history are deleted by the database CASCADE constraints,
+/// the tombstones are kept.
+pub fn purge_trash(conn: &mut PgConnection, retention_days: i64) -> Result<usize, Error> {
+    use crate::schema::glossary::dsl::*;
+
+    let threshold = Utc::now().naive_utc() - Duration::days(retention_days);
+    let purged = diesel::delete(glossary.filter(deleted_at.lt(threshold))).execute(conn)?;
+
+    info!("Purged {} glossaries from the trash", purged);
+    Ok(purged)
+}
+
+/// List glossaries in the trash, most recently deleted first
+#[get("/trash")]
+pub async fn list(pool: web::Data<DBPool>) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+
+    let glossaries = web::block(move || list_trash(&mut conn)).await??;
+    let glossaries: Vec<Glossary> = glossaries.iter().map(|g| g.to_glossary()).collect();
+
+    Ok(web::Json(Glossaries::from(&glossaries)))
+}
+
+/// Restore a glossary from the trash
+#[post("/glossary/{id}/restore")]
+pub async fn restore(
+    pool: web::Data<DBPool>,
+    id: web::Path<String>,
+    req: HttpRequest,
+) -> actix_web::Result<impl Responder, ApiError> {
+    let who = req
+        .headers()
+        .get(crate::AUTHENTICATED_USER_HEADER)
+        .map(|email| email.to_str().unwrap().to_string());
+    let who2 = who.clone();
+
+    let glossary_id = Uuid::from_str(&id)
+        .map_err(|_| ApiError::invalid_input("Invalid glossary ID format"))?;
+
+    let (glossary, tags) = web::block(move || {
+        let mut conn = pool.get().expect("could not get db connection from pool");
+        let restored = restore_glossary(&mut conn, glossary_id, who)?;
+        let tags = list_glossary_tag_names(&mut conn, glossary_id)?;
+        Ok::<_, Error>((restored, tags))
+    })
+    .await??;
+
+    Ok(web::Json(glossary.to_glossary_with_who(who2).add_tags(tags)))
+}
+
+/// Purge glossaries which are in the trash for longer than the retention period
+#[delete("/trash")]
+pub async fn purge(pool: web::Data<DBPool>) -> actix_web::Result<impl Responder, ApiError> {
+    let mut conn = pool.get().expect("could not get db connection from pool");
+    let retention_days = trash_retention_days();
+
+    let purged = web::block(move || purge_trash(&mut conn, retention_days)).await??;
+    Ok(web::Json(PurgeResp {
+        purged,
+        retention_days,
+    }))
+}
+
+// Tests
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{service_should_ok_and_return_json, TestContext};
+    use crate::v1::glossary::{self, GlossaryRequest};
+    use crate::v1::like::{create_like, list_likes};
+    use actix_web::{http, test, App};
+
+    // Delete a glossary: it is hidden from the list but in the trash,
+    // with its likes. Restore it: it is back in the list.
+    #[actix_rt::test]
+    async fn delete_then_restore() {
+        let ctx = TestContext::new("trash_delete_then_restore");
+        let pool = ctx.get_pool();
+        let mut conn = pool.get().expect("could not get db connection from pool");
+
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(pool))
+                .service(glossary::list)
+                .service(glossary::create)
+                .service(glossary::delete)
+                .service(list)
+                .service(restore),
+        )
+        .await;
+
+        let req = test::TestRequest::post().uri("/glossary").set_json(GlossaryRequest {
+            term: Some("test_term".to_string()),
+            definition: Some("test_definition".to_string()),
+            ..Default::default()
+        });
+        let resp = service_should_ok_and_return_json!(app, req);
+        let created: Glossary = test::read_body_json(resp).await;
+        let glossary_id = Uuid::from_str(&created.id).unwrap();
+        let _ = create_like(&mut conn, glossary_id, "reader@example.com");
+
+        let req = test::TestRequest::delete().uri(&format!("/glossary/{}", created.id));
+        let _ = service_should_ok_and_return_json!(app, req);
+
+        let req = test::TestRequest::get().uri("/glossary");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let glossaries: Glossaries = test::read_body_json(resp).await;
+        assert_eq!(glossaries.total, Some(0));
+
+        let req = test::TestRequest::get().uri("/trash");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let trash: Glossaries = test::read_body_json(resp).await;
+        assert_eq!(trash.count, 1);
+        assert!(trash.results[0].deleted_at.is_some());
+
+        // Likes are kept in the trash
+        assert_eq!(list_likes(&mut conn, glossary_id).unwrap().len(), 1);
+
+        let req = test::TestRequest::post().uri(&format!("/glossary/{}/restore", created.id));
+        let resp = service_should_ok_and_return_json!(app, req);
+        let restored: Glossary = test::read_body_json(resp).await;
+        assert!(restored.deleted_at.is_none());
+        // create, delete, restore
+        assert_eq!(restored.revision, 2);
+
+        let req = test::TestRequest::get().uri("/glossary");
+        let resp = service_should_ok_and_return_json!(app, req);
+        let glossaries: Glossaries = test::read_body_json(resp).await;
+        assert_eq!(glossaries.total, Some(1));
+
+        // Not in the trash anymore
+        let req = test::TestRequest::post()
+            .uri(&format!("/glossary/{}/restore", created.id))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+        assert_eq!(resp.status(), http::StatusCode::NOT_FOUND);
+    }
+
+    // Purge hard deletes glossaries older than the retention period only
+    #[actix_rt::test]
+    async fn purge_after_retention() {
+        use crate::schema::glossary::dsl;
+
+        let ctx = TestContext::new("trash_purge_after_retention");
+        let pool = ctx.get_pool();
+        let conn = &mut pool.get().expect("could not get db connection from pool");
+
+        let old_id = Uuid::new_v4();
+        let recent_id = Uuid::new_v4();
+        for (glossary_id, term, days) in [(old_id, "old", 40), (recent_id, "recent", 1)] {
+            diesel::insert_into(dsl::glossary)
+                .values(GlossaryDB {
+                    id: glossary_id,
+                    term: term.to_string(),
+                    definition: "definition".to_string(),
+                    deleted_at: Some(Utc::now().naive_utc() - Duration::days(days)),
+                    ..Default::default()
+                })
+                .execute(conn)
+                .expect("could not insert glossary");
+        }
+
+        assert_eq!(purge_trash(conn, 30).unwrap(), 1);
+
+        let remaining = list_trash(conn).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, recent_id);
+    }
+}
@@ -4,7 +4,9 @@ const API_BASE = '/api/v1';
 // State
 let allGlossary = {};
 let searchTimeout = null;
+let suggestTimeout = null;
 let currentEditId = null;
+let currentEditRevision = null;
 
 // Theme Management
 function initTheme() {
@@ -22,7 +24,9 @@ function toggleTheme() {
 // API Functions
 async function fetchGlossary() {
     try {
-        const response = await fetch(`${API_BASE}/glossary`);
+        const response = await fetch(`${API_BASE}/glossary?view=grouped`, {
+            headers: { 'x-authenticated-user-email': getUserEmail() }
+        });
         if (!response.ok) throw new Error('Failed to fetch glossary');
         return await response.json();
     } catch (error) {
@@ -32,14 +36,40 @@ async function fetchGlossary() {
     }
 }
 
+// The term in its source language, whatever the browser language.
+// Editing a translated term would save the translation over the source.
+async function fetchSourceTerm(id) {
+    const response = await fetch(`${API_BASE}/glossary/${id}`, {
+        headers: {
+            'Accept-Language': '*',
+            'x-authenticated-user-email': getUserEmail()
+        }
+    });
+    if (!response.ok) throw new Error('Failed to fetch term');
+    return await response.json();
+}
+
 async function searchGlossary(query) {
     try {
-        const response = await fetch(`${API_BASE}/glossary-search?q=${encodeURIComponent(query)}`);
+        const response = await fetch(`${API_BASE}/glossary-search?q=${encodeURIComponent(query)}`, {
+            headers: { 'x-authenticated-user-email': getUserEmail() }
+        });
         if (!response.ok) throw new Error('Search failed');
         return await response.json();
     } catch (error) {
         console.error('Error searching:', error);
-        return { results: [], count: 0 };
+        return { results: [], count: 0, total: 0 };
+    }
+}
+
+async function suggestTerms(prefix) {
+    try {
+        const response = await fetch(`${API_BASE}/glossary-suggest?prefix=${encodeURIComponent(prefix)}&limit=8`);
+        if (!response.ok) throw new Error('Suggest failed');
+        return await response.json();
+    } catch (error) {
+        console.error('Error suggesting terms:', error);
+        return [];
     }
 }
 
@@ -54,7 +84,7 @@ async function fetchPopular() {
     }
 }
 
-async function createTerm(term, definition) {
+async function createTerm(term, definition, expansion) {
     try {
         const response = await fetch(`${API_BASE}/glossary`, {
             method: 'POST',
@@ -62,17 +92,20 @@ async function createTerm(term, definition) {
                 'Content-Type': 'application/json',
                 'x-authenticated-user-email': getUserEmail()
             },
-            body: JSON.stringify({ term, definition })
+            body: JSON.stringify({ term, definition, expansion })
         });
         if (!response.ok) throw new Error('Failed to create term');
-        return await response.json();
+        const created = await response.json();
+        // e.g. an acronym created without its expansion
+        (created.warnings || []).forEach(warning => console.warn(warning));
+        return created;
     } catch (error) {
         console.error('Error creating term:', error);
         throw error;
     }
 }
 
-async function updateTerm(id, term, definition) {
+async function updateTerm(id, term, definition, expansion, revision) {
     try {
         const response = await fetch(`${API_BASE}/glossary/${id}`, {
             method: 'PUT',
@@ -80,8 +113,13 @@ async function updateTerm(id, term, definition) {
                 'Content-Type': 'application/json',
                 'x-authenticated-user-email': getUserEmail()
             },
-            body: JSON.stringify({ term, definition })
+            body: JSON.stringify({ term, definition, expansion, revision })
         });
+        if (response.status === 409) {
+            const conflict = await response.json();
+            const current = conflict.details || {};
+            throw new Error(`Someone else saved revision ${current.revision} in the meantime:\n\n${current.definition}`);
+        }
         if (!response.ok) throw new Error('Failed to update term');
         return await response.json();
     } catch (error) {
@@ -90,10 +128,11 @@ async function updateTerm(id, term, definition) {
     }
 }
 
-async function likeTerm(id) {
+async function likeTerm(id, liked) {
     try {
+        // Likes are one per user: like, or take back our own like
         const response = await fetch(`${API_BASE}/glossary/${id}/likes`, {
-            method: 'POST',
+            method: liked ? 'DELETE' : 'POST',
             headers: {
                 'Content-Type': 'application/json',
                 'x-authenticated-user-email': getUserEmail()
@@ -108,6 +147,20 @@ async function likeTerm(id) {
     }
 }
 
+async function reactToTerm(id, kind) {
+    try {
+        const response = await fetch(`${API_BASE}/glossary/${id}/reactions/${kind}`, {
+            method: 'POST',
+            headers: { 'x-authenticated-user-email': getUserEmail() }
+        });
+        if (!response.ok) throw new Error('Failed to react to term');
+        return await response.json();
+    } catch (error) {
+        console.error('Error reacting to term:', error);
+        throw error;
+    }
+}
+
 function getUserEmail() {
     // In a real app, this would come from authentication
     return localStorage.getItem('userEmail') || 'user@example.com';
@@ -156,6 +209,28 @@ function createTermCard(term) {
     title.textContent = term.term;
     card.appendChild(title);
 
+    if (term.status && term.status !== 'active') {
+        card.classList.add(`term-${term.status}`);
+        const status = document.createElement('small');
+        status.className = 'term-status';
+        status.textContent = `${term.status}: ${term.status_reason || ''}`;
+        card.appendChild(status);
+    }
+
+    if (term.expansion && term.expansion !== term.term) {
+        const expansion = document.createElement('small');
+        expansion.className = 'term-aliases';
+        expansion.textContent = term.expansion;
+        card.appendChild(expansion);
+    }
+
+    if (term.aliases && term.aliases.length > 0) {
+        const aliases = document.createElement('small');
+        aliases.className = 'term-aliases';
+        aliases.textContent = `also: ${term.aliases.join(', ')}`;
+        card.appendChild(aliases);
+    }
+
     const definition = document.createElement('p');
     definition.textContent = term.definition;
     card.appendChild(definition);
@@ -163,15 +238,34 @@ function createTermCard(term) {
     const meta = document.createElement('div');
     meta.className = 'term-meta';
     meta.innerHTML = `
-        <span>❤️ ${term.likes_count || 0} likes</span>
+        <span>${term.liked_by_me ? '❤️' : '🤍'} ${term.likes_count || 0} likes</span>
         ${term.who ? `<span>👤 ${term.who}</span>` : ''}
     `;
     card.appendChild(meta);
 
-    // Click to like
+    // Flag a definition as unclear or outdated for the term stewards
+    const reactions = term.reactions || {};
+    [['unclear', '❓'], ['outdated', '⚠️']].forEach(([kind, emoji]) => {
+        const button = document.createElement('span');
+        button.className = 'term-reaction';
+        button.title = `Mark as ${kind}`;
+        button.textContent = `${emoji} ${reactions[kind] || 0}`;
+        button.addEventListener('click', async (e) => {
+            e.stopPropagation();
+            try {
+                await reactToTerm(term.id, kind);
+                loadGlossary(); // Refresh
+            } catch (error) {
+                showError(`Failed to mark term as ${kind}`);
+            }
+        });
+        meta.appendChild(button);
+    });
+
+    // Click to like, click again to unlike
     card.addEventListener('click', async () => {
         try {
-            await likeTerm(term.id);
+            await likeTerm(term.id, term.liked_by_me);
             loadGlossary(); // Refresh
         } catch (error) {
             console.error('Error liking term:', error);
@@ -201,7 +295,7 @@ function renderSearchResults(results) {
 
     container.style.display = 'block';
     document.getElementById('alphabetGrid').style.display = 'none';
-    document.getElementById('searchStats').textContent = `Found ${results.count} term${results.count !== 1 ? 's' : ''}`;
+    document.getElementById('searchStats').textContent = `Found ${results.total} term${results.total !== 1 ? 's' : ''}`;
 }
 
 async function renderPopular() {
@@ -240,22 +334,34 @@ async function renderPopular() {
 }
 
 // Modal Functions
-function openModal(editMode = false, term = null) {
+async function openModal(editMode = false, term = null) {
     const modal = document.getElementById('termModal');
     const title = document.getElementById('modalTitle');
     const termInput = document.getElementById('termName');
     const defInput = document.getElementById('termDefinition');
+    const expansionInput = document.getElementById('termExpansion');
 
     if (editMode && term) {
+        try {
+            term = await fetchSourceTerm(term.id);
+        } catch (error) {
+            showError('Failed to load term');
+            return;
+        }
+
         title.textContent = 'Edit Term';
         termInput.value = term.term;
+        expansionInput.value = term.expansion || '';
         defInput.value = term.definition;
         currentEditId = term.id;
+        currentEditRevision = term.revision;
     } else {
         title.textContent = 'New Term';
         termInput.value = '';
+        expansionInput.value = '';
         defInput.value = '';
         currentEditId = null;
+        currentEditRevision = null;
     }
 
     modal.classList.add('active');
@@ -271,19 +377,55 @@ async function handleFormSubmit(e) {
 
     const term = document.getElementById('termName').value.trim();
     const definition = document.getElementById('termDefinition').value.trim();
+    const expansion = document.getElementById('termExpansion').value.trim();
 
     try {
         if (currentEditId) {
-            await updateTerm(currentEditId, term, definition);
+            await updateTerm(currentEditId, term, definition, expansion, currentEditRevision);
         } else {
-            await createTerm(term, definition);
+            await createTerm(term, definition, expansion || undefined);
         }
 
         closeModal();
         await loadGlossary();
     } catch (error) {
-        showError('Failed to save term');
+        showError(error.message || 'Failed to save term');
+    }
+}
+
+function renderSuggestions(suggestions) {
+    const datalist = document.getElementById('searchSuggestions');
+    datalist.innerHTML = '';
+
+    suggestions.forEach(suggestion => {
+        const option = document.createElement('option');
+        option.value = suggestion.term;
+        datalist.appendChild(option);
+    });
+}
+
+// Typeahead Handler: lightweight prefix lookups on every keystroke
+function handleSuggest() {
+    const query = document.getElementById('searchInput').value.trim();
+
+    clearTimeout(suggestTimeout);
+    if (!query) {
+        renderSuggestions([]);
+        handleSearch();
+        return;
     }
+    document.getElementById('searchClear').style.display = 'block';
+
+    // Picking a suggestion runs the full search straight away
+    const options = document.getElementById('searchSuggestions').options;
+    if (Array.from(options).some(option => option.value === query)) {
+        handleSearch();
+        return;
+    }
+
+    suggestTimeout = setTimeout(async () => {
+        renderSuggestions(await suggestTerms(query));
+    }, 150);
 }
 
 // Search Handler
@@ -365,12 +507,15 @@ document.addEventListener('DOMContentLoaded', () => {
 
     // Search
     const searchInput = document.getElementById('searchInput');
-    searchInput.addEventListener('input', handleSearch);
+    searchInput.addEventListener('input', handleSuggest);
+    searchInput.addEventListener('keydown', (e) => {
+        if (e.key === 'Enter') handleSearch();
+    });
 
     // Clear search
     document.getElementById('searchClear').addEventListener('click', () => {
         searchInput.value = '';
-        handleSearch();
+        handleSuggest();
     });
 
     // Empty state button